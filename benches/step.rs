@@ -0,0 +1,45 @@
+//! Measures how much lazy screen fetching (see `Ale::set_lazy_screen`) speeds up stepping Breakout, depending on how
+//! often the screen is observed, compared to observing the RAM, which never reads the screen.
+
+use std::hint::black_box;
+
+use ale::{Ale, AleConfig, BundledRom, LoggerMode, ObservationType};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const STEPS: u64 = 1_000;
+
+fn step(c: &mut Criterion) {
+	Ale::set_logger_mode(LoggerMode::Error);
+	let mut group = c.benchmark_group("step");
+	group.throughput(Throughput::Elements(STEPS));
+	for &observe_every in &[1, 4, u64::MAX] {
+		let label = if observe_every == u64::MAX { "never".to_string() } else { format!("1/{}", observe_every) };
+		let variants = [
+			("eager", false, ObservationType::Rgb),
+			("lazy", true, ObservationType::Rgb),
+			("ram", false, ObservationType::Ram),
+		];
+		for &(name, lazy_screen, observation_type) in &variants {
+			let config = AleConfig::new().seed(0).lazy_screen(lazy_screen).observation_type(observation_type);
+			let mut ale = config.build(BundledRom::Breakout).expect("load failed");
+			let n = ale.action_dim();
+			group.bench_function(BenchmarkId::new(name, &label), |b| {
+				b.iter(|| {
+					for i in 0..STEPS {
+						let result = ale.step((i % n as u64) as u8).expect("invalid action");
+						if i % observe_every == 0 {
+							black_box(ale.observation());
+						}
+						if result.done() {
+							ale.reset_game();
+						}
+					}
+				})
+			});
+		}
+	}
+	group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
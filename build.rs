@@ -0,0 +1,59 @@
+//! Compresses the bundled ROMs when the `compressed-roms` feature is enabled. See `src/rom/compressed.rs`.
+
+fn main() {
+	println!("cargo:rerun-if-changed=build.rs");
+	#[cfg(feature = "compressed-roms")]
+	compressed_roms::write().expect("failed to compress the bundled ROMs");
+}
+
+#[cfg(feature = "compressed-roms")]
+mod compressed_roms {
+	use std::env;
+	use std::fmt::Write as _;
+	use std::io::{self, Write as _};
+	use std::path::PathBuf;
+
+	use flate2::write::DeflateEncoder;
+	use flate2::Compression;
+
+	/// Writes `roms.deflate` and `roms_index.rs` to `OUT_DIR`, containing every ROM in `roms/` whose feature is
+	/// enabled.
+	pub fn write() -> io::Result<()> {
+		let roms_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("roms");
+		let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+		println!("cargo:rerun-if-changed={}", roms_dir.display());
+
+		let mut files = vec![];
+		if env::var_os("CARGO_FEATURE_NO_BUNDLED_ROMS").is_none() {
+			for entry in std::fs::read_dir(&roms_dir)? {
+				let path = entry?.path();
+				let file = match path.file_name().and_then(|name| name.to_str()) {
+					Some(file) if file.ends_with(".bin") => file.to_owned(),
+					_ => continue,
+				};
+				// e.g. `ms_pacman.bin` is bundled by the `rom-ms-pacman` feature
+				let feature = format!("CARGO_FEATURE_ROM_{}", file.trim_end_matches(".bin").to_uppercase());
+				if env::var_os(feature).is_some() {
+					println!("cargo:rerun-if-changed={}", path.display());
+					files.push((file, path));
+				}
+			}
+		}
+		files.sort();
+
+		let mut blob = vec![];
+		let mut index = String::from("static INDEX: &[(&str, usize, usize, usize)] = &[\n");
+		for (file, path) in files {
+			let data = std::fs::read(&path)?;
+			let start = blob.len();
+			let mut encoder = DeflateEncoder::new(&mut blob, Compression::best());
+			encoder.write_all(&data)?;
+			encoder.finish()?;
+			writeln!(index, "\t({:?}, {}, {}, {}),", file, start, blob.len(), data.len()).unwrap();
+		}
+		index.push_str("];\n");
+
+		std::fs::write(out_dir.join("roms.deflate"), blob)?;
+		std::fs::write(out_dir.join("roms_index.rs"), index)
+	}
+}
@@ -1,36 +1,32 @@
-
-
-use ale::{Ale, BundledRom, LoggerMode};
+use ale::{Ale, AleConfig, BundledRom, LoggerMode, VecEnv};
 use rand::prelude::*;
-use rayon::prelude::*;
 
-fn main() {
-    let mut envs = vec![];
-    let num_envs = 16;
-    Ale::set_logger_mode(LoggerMode::Error);
-    for _ in 0..num_envs {
-        let mut env = Ale::new(108_000);
-        let e = env.load_rom(BundledRom::Breakout);
-        match e {
-            Ok(_) => (),
-            Err(e) => panic!("Failed to load ROM: {:?}", e)
-        }
-        envs.push(env);
-    }
+const NUM_ENVS: usize = 16;
+const STEPS: u32 = 10_000;
 
-    let n = envs[0].action_dim();
-    let start_time = std::time::Instant::now();
-    for _ in 0..10000 {
-        envs.par_iter_mut().for_each(|e| {
-            let action = thread_rng().gen_range(0..n);
-            e.act(action);
-            if e.is_game_over() {
-                e.reset_game();
-            }
-        });
-    }
-    let elapsed = start_time.elapsed();
-    println!("Time elapsed: {:?}", elapsed);
+fn main() {
+	Ale::set_logger_mode(LoggerMode::Error);
+	let config = AleConfig::new().seed(0).lazy_screen(true);
+	let mut envs = VecEnv::new(&config, BundledRom::Breakout, NUM_ENVS).expect("failed to load ROM");
+	envs.reset(Some(0)).expect("failed to reset");
 
+	let n = envs.envs()[0].action_dim();
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut actions = [0; NUM_ENVS];
+	let mut episodes = 0;
+	let start_time = std::time::Instant::now();
+	for _ in 0..STEPS {
+		actions.iter_mut().for_each(|action| *action = rng.gen_range(0..n));
+		let step = envs.step(&actions).expect("invalid action");
+		episodes += (0..NUM_ENVS).filter(|&i| step.done(i)).count();
+	}
+	let elapsed = start_time.elapsed();
+	println!(
+		"{} steps of {} envs in {:?} ({:.0} steps/s), {} episodes finished",
+		STEPS,
+		NUM_ENVS,
+		elapsed,
+		(STEPS as usize * NUM_ENVS) as f64 / elapsed.as_secs_f64(),
+		episodes
+	);
 }
-
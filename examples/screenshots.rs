@@ -22,6 +22,6 @@ fn main() {
 			ale.save_screen_png(&filename_cstr);
 		}
 		let action = thread_rng().gen_range(0..n);
-		ale.act(action);
+		ale.act(action).expect("invalid action");
 	}
 }
@@ -0,0 +1,30 @@
+#![no_main]
+use ale::{AleError, AleState};
+use libfuzzer_sys::fuzz_target;
+
+const HEADER_LEN: usize = AleState::HEADER_LEN;
+
+// Fuzzes the header checks of `AleState::decode_state`. The first `HEADER_LEN` bytes of the input are XORed into a
+// valid header, so that every field gets corrupted rather than mostly the magic bytes, and the rest is the payload.
+// Only inputs that leave the checked fields intact reach the ALE, see the `restore_state` target for fuzzing it.
+fuzz_target!(|data: &[u8]| {
+	let mut mask = [0; HEADER_LEN];
+	let (head, payload) = data.split_at(data.len().min(HEADER_LEN));
+	mask[..head.len()].copy_from_slice(head);
+	let mut encoded = AleState::encode_payload(Some([0xa5; 16]), false, payload);
+	for (byte, mask) in encoded.iter_mut().zip(&mask) {
+		*byte ^= mask;
+	}
+	// The magic bytes, version, unknown flags, payload checksum and length are checked, the ROM checksum is not
+	let checked_intact = mask[..6].iter().chain(&mask[23..]).all(|&mask| mask == 0) && mask[6] & !0b11 == 0;
+	match AleState::decode_state(&encoded) {
+		Ok(state) => {
+			assert!(checked_intact, "a corrupted header was accepted");
+			assert_eq!(state.is_system(), encoded[6] & 1 != 0);
+			assert_eq!(state.rom_md5().is_some(), encoded[6] & 2 != 0);
+		}
+		// The ALE may still reject the payload behind a valid header
+		Err(AleError::StateDecode(_)) => {}
+		Err(e) => panic!("unexpected error: {}", e),
+	}
+});
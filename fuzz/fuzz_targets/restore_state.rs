@@ -0,0 +1,25 @@
+#![no_main]
+use ale::{Ale, AleState, BundledRom};
+use libfuzzer_sys::fuzz_target;
+
+// Fuzzes the ALE's own decoding and restoring of states, by putting the input behind a valid header for a state of
+// Breakout. The ALE does not validate states, so this is expected to find crashes in the ALE itself, which this crate
+// only guards against for corrupted data, through the checksum in the header.
+fuzz_target!(|data: &[u8]| {
+	let (system, payload) = match data.split_first() {
+		Some((&flags, payload)) => (flags & 1 != 0, payload),
+		None => return,
+	};
+	let rom_md5 = md5::compute(BundledRom::Breakout.data().unwrap()).0;
+	let encoded = AleState::encode_payload(Some(rom_md5), system, payload);
+	// Any error comes from the ALE rejecting the payload
+	if let Ok(state) = AleState::decode_state(&encoded) {
+		let mut ale = Ale::new(0);
+		ale.load_rom(BundledRom::Breakout).unwrap();
+		let restored = if system { ale.restore_system_state(&state) } else { ale.restore_state(&state) };
+		restored.expect("the state was encoded for the loaded ROM");
+		for _ in 0..10 {
+			ale.act(0).unwrap();
+		}
+	}
+});
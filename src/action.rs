@@ -0,0 +1,103 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::os::raw::c_int;
+
+/// An input that can be sent to the Atari, mirroring the ALE's `Action` enum.
+///
+/// The discriminant of each variant is the value the ALE uses for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+	Noop = 0,
+	Fire = 1,
+	Up = 2,
+	Right = 3,
+	Left = 4,
+	Down = 5,
+	UpRight = 6,
+	UpLeft = 7,
+	DownRight = 8,
+	DownLeft = 9,
+	UpFire = 10,
+	RightFire = 11,
+	LeftFire = 12,
+	DownFire = 13,
+	UpRightFire = 14,
+	UpLeftFire = 15,
+	DownRightFire = 16,
+	DownLeftFire = 17,
+}
+impl Action {
+	/// Every action, in the order the ALE defines them. This is the full action set.
+	pub const ALL: [Action; 18] = [
+		Action::Noop,
+		Action::Fire,
+		Action::Up,
+		Action::Right,
+		Action::Left,
+		Action::Down,
+		Action::UpRight,
+		Action::UpLeft,
+		Action::DownRight,
+		Action::DownLeft,
+		Action::UpFire,
+		Action::RightFire,
+		Action::LeftFire,
+		Action::DownFire,
+		Action::UpRightFire,
+		Action::UpLeftFire,
+		Action::DownRightFire,
+		Action::DownLeftFire,
+	];
+
+	/// Returns the name the ALE uses for this action, e.g. `"UPFIRE"`.
+	pub fn name(&self) -> &'static str {
+		use Action::*;
+		match self {
+			Noop => "NOOP",
+			Fire => "FIRE",
+			Up => "UP",
+			Right => "RIGHT",
+			Left => "LEFT",
+			Down => "DOWN",
+			UpRight => "UPRIGHT",
+			UpLeft => "UPLEFT",
+			DownRight => "DOWNRIGHT",
+			DownLeft => "DOWNLEFT",
+			UpFire => "UPFIRE",
+			RightFire => "RIGHTFIRE",
+			LeftFire => "LEFTFIRE",
+			DownFire => "DOWNFIRE",
+			UpRightFire => "UPRIGHTFIRE",
+			UpLeftFire => "UPLEFTFIRE",
+			DownRightFire => "DOWNRIGHTFIRE",
+			DownLeftFire => "DOWNLEFTFIRE",
+		}
+	}
+}
+impl TryFrom<c_int> for Action {
+	type Error = c_int;
+
+	/// Converts a raw ALE action value, returning the value back if it is not a known action.
+	fn try_from(value: c_int) -> Result<Action, c_int> {
+		if value >= 0 && (value as usize) < Action::ALL.len() {
+			Ok(Action::ALL[value as usize])
+		} else {
+			Err(value)
+		}
+	}
+}
+impl fmt::Display for Action {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.name()) }
+}
+
+/// Which set of actions [`Ale::act`](crate::Ale::act) indexes into.
+///
+/// Defaults to [`ActionSet::Full`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ActionSet {
+	/// The minimal set of actions needed to play the loaded game.
+	Minimal,
+	/// All 18 actions, regardless of the game.
+	#[default]
+	Full,
+}
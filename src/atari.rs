@@ -14,21 +14,42 @@
 //! # Unsafety
 //! Generally this libarary has tried to encapsulate and minimize unsafety, but there could still be some pain points that I've missed (especially regarding C++ exceptions). Be sure to report an issue if this is the case!
 
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::ffi::{CStr, CString};
-use std::io;
+#[cfg(unix)]
+use std::ffi::OsStr;
 use std::os::raw::c_int;
-use std::ptr::null_mut;
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::check_buffer;
+use crate::{
+	state_file, Action, ActionSet, AleError, AleState, BundledRom, EnvSnapshot, ObservationType, Result, Setting,
+	SettingKey, StateFileHeader, StateFileOptions, StepResult,
+};
 
 /// Interface to the Arcade Learning Environment emulator
 pub struct Ale {
 	ptr: *mut ale_sys::ALEInterface,
-	action_set: Vec<i32>,
+	action_set: Vec<Action>,
+	action_set_kind: ActionSet,
 	screen_data: Vec<u8>,
 	screen_size: (u16, u16),
+	lazy_screen: bool,
+	screen_dirty: bool,
+	observation_type: ObservationType,
+	observation_shape: Vec<usize>,
+	observation_data: Vec<u8>,
+	observation_dirty: bool,
 	score: i32,
 	max_frames: u32,
 	lives: u8,
+	rom_md5: Option<[u8; 16]>,
+	rom_path: Option<PathBuf>,
+	mode: Option<i32>,
+	difficulty: Option<i32>,
 }
 
 unsafe impl Send for Ale {
@@ -41,40 +62,105 @@ unsafe impl Sync for Ale {
 
 impl Ale {
 	/// Creates a new interface to the Arcade Learning Environment, i.e. a new emulator instance.
+	///
+	/// # Panics
+	/// Panics if the emulator could not be created. See [`Ale::try_new`] for a version that returns an error instead.
 	pub fn new(max_frames: u32) -> Ale {
+		Ale::try_new(max_frames).expect("failed to create an ALE instance")
+	}
+
+	/// Creates a new interface to the Arcade Learning Environment, i.e. a new emulator instance.
+	///
+	/// [`Ale::step`] reports an episode as truncated once it has lasted `max_frames` frames, see
+	/// [`AleConfig::truncate_episodes_after`](crate::AleConfig::truncate_episodes_after).
+	///
+	/// Returns [`AleError::Create`] if the ALE failed to create the emulator.
+	pub fn try_new(max_frames: u32) -> Result<Ale> {
 		let ptr = unsafe { ale_sys::ALE_new() };
-		assert!(ptr != null_mut());
+		if ptr.is_null() {
+			return Err(AleError::Create("ALE_new returned a null pointer".into()));
+		}
 		let screen_size = unsafe{(
 			ale_sys::getScreenHeight(ptr) as u16, 
 			ale_sys::getScreenWidth(ptr) as u16)};
-		let action_set = unsafe {
-			let n = ale_sys::getLegalActionSize(ptr);
-			let mut set = vec![0i32; n as usize];
-			ale_sys::getLegalActionSet(ptr, set.as_mut_ptr());
-			set
-		};
-		let screen_data = vec![0u8; (screen_size.0 * screen_size.1 * 3) as usize];
+		let screen_data = vec![0u8; screen_size.0 as usize * screen_size.1 as usize * 3];
 		let score = 0;
 		let lives = 0;
-		Ale { ptr, action_set, screen_data, screen_size, score, max_frames, lives}
+		let mut ale = Ale {
+			ptr,
+			action_set: vec![],
+			action_set_kind: ActionSet::default(),
+			screen_data,
+			screen_size,
+			lazy_screen: false,
+			screen_dirty: false,
+			observation_type: ObservationType::Rgb,
+			observation_shape: vec![screen_size.0 as usize, screen_size.1 as usize, 3],
+			observation_data: vec![],
+			observation_dirty: false,
+			score,
+			max_frames,
+			lives,
+			rom_md5: None,
+			rom_path: None,
+			mode: None,
+			difficulty: None,
+		};
+		ale.refresh_action_set()?;
+		Ok(ale)
 	}
 
-
-
-	/// Applies an action to the game and returns the reward.
+	/// Applies the action at index `action` of the current action set to the game and returns the reward.
 	///
 	/// It is the user's responsibility to check if the game has ended and reset
 	/// when necessary - this method will keep pressing buttons on the game over screen.
-	pub fn act(&mut self, action: u8) -> (i32, bool, bool) {
+	///
+	/// Returns [`AleError::InvalidAction`] if `action` is not less than [`Ale::action_dim`].
+	pub fn act(&mut self, action: u8) -> Result<i32> {
+		let action = self.action_at(action)?;
+		Ok(self.act_action(action))
+	}
+
+	/// Applies an action to the game and returns the reward, regardless of whether it is in the current action set.
+	pub fn act_action(&mut self, action: Action) -> i32 {
 		unsafe {
-			let a = self.action_set.iter().nth(action as usize).unwrap();
-			let r = ale_sys::act(self.ptr, *a);
+			let r = ale_sys::act(self.ptr, action as c_int);
 			self.score += r;
-			ale_sys::getScreenRGB(self.ptr, self.screen_data.as_mut_ptr());
-			let live_loss = (self.current_lives() as u8) < self.lives;
+			self.screen_changed();
 			self.lives = self.current_lives() as u8;
-			let truncation = self.episode_frame_number() as u32 >= self.max_frames;
-			(r, live_loss, truncation)
+			r
+		}
+	}
+
+	/// Applies the action at index `action` of the current action set to the game, and returns the reward along with
+	/// whether the episode has terminated or been truncated.
+	///
+	/// Returns [`AleError::InvalidAction`] if `action` is not less than [`Ale::action_dim`].
+	pub fn step(&mut self, action: u8) -> Result<StepResult> {
+		let action = self.action_at(action)?;
+		Ok(self.step_action(action))
+	}
+
+	/// Same as [`Ale::step`], but takes an action directly, regardless of whether it is in the current action set.
+	pub fn step_action(&mut self, action: Action) -> StepResult {
+		let prev_lives = self.lives;
+		let reward = self.act_action(action);
+		let episode_frame_number = self.episode_frame_number();
+		StepResult {
+			reward,
+			terminated: self.is_game_over(),
+			truncated: episode_frame_number as u32 >= self.max_frames,
+			life_lost: self.lives < prev_lives,
+			lives: self.lives.into(),
+			episode_frame_number,
+			frame_number: self.frame_number(),
+		}
+	}
+
+	fn action_at(&self, index: u8) -> Result<Action> {
+		match self.action_set.get(index as usize) {
+			Some(&a) => Ok(a),
+			None => Err(AleError::InvalidAction { index, action_dim: self.action_dim() }),
 		}
 	}
 
@@ -84,74 +170,295 @@ impl Ale {
 			ale_sys::reset_game(self.ptr);
 			self.score = 0;
 			self.lives = self.current_lives() as u8;
-			ale_sys::getScreenRGB(self.ptr, self.screen_data.as_mut_ptr());
+			self.screen_changed();
 		}
 	}
 
-	pub fn action_dim(&mut self) -> u8 {
+	/// Returns the number of actions in the current action set, i.e. the number of valid indices for [`Ale::act`].
+	pub fn action_dim(&self) -> u8 {
 		self.action_set.len() as u8
 	}
 
+	/// Returns the actions that [`Ale::act`] indexes into.
+	pub fn action_set(&self) -> &[Action] {
+		&self.action_set
+	}
+
+	/// Returns which kind of action set [`Ale::act`] is currently using.
+	pub fn action_set_kind(&self) -> ActionSet {
+		self.action_set_kind
+	}
+
+	/// Selects the action set that [`Ale::act`] indexes into.
+	///
+	/// The set is recomputed from the loaded game immediately, and again every time a ROM is loaded.
+	pub fn set_action_set(&mut self, kind: ActionSet) -> Result<()> {
+		self.action_set_kind = kind;
+		self.refresh_action_set()
+	}
+
+	fn refresh_action_set(&mut self) -> Result<()> {
+		let raw = match self.action_set_kind {
+			ActionSet::Minimal => self.minimal_action_set()?,
+			ActionSet::Full => self.legal_action_set()?,
+		};
+		self.action_set = raw.into_iter().filter_map(|a| Action::try_from(a).ok()).collect();
+		Ok(())
+	}
+
+	/// Returns the kind of observation returned by [`Ale::observation`].
+	pub fn observation_type(&self) -> ObservationType {
+		self.observation_type
+	}
+
+	/// Returns the shape of the observations returned by [`Ale::observation`]. Observations are always `u8`s.
+	pub fn observation_shape(&self) -> &[usize] {
+		&self.observation_shape
+	}
+
+	/// Sets the kind of observation returned by [`Ale::observation`].
+	pub fn set_observation_type(&mut self, observation_type: ObservationType) -> Result<()> {
+		let (height, width) = (self.screen_size.0 as usize, self.screen_size.1 as usize);
+		self.observation_shape = match observation_type {
+			ObservationType::Rgb => vec![height, width, 3],
+			ObservationType::Grayscale | ObservationType::Palette => vec![height, width],
+			ObservationType::Ram => vec![self.ram_size()?],
+		};
+		self.observation_type = observation_type;
+		self.observation_data = match observation_type {
+			// RGB observations are served from the screen buffer
+			ObservationType::Rgb => vec![],
+			_ => vec![0; self.observation_shape.iter().product()],
+		};
+		self.observation_dirty = true;
+		if self.screen_dirty && self.eager_screen() {
+			self.read_screen();
+		}
+		Ok(())
+	}
+
+	/// Returns the current observation, in the format selected by [`Ale::set_observation_type`].
+	///
+	/// The observation is only read from the emulator if it has changed since it was last read.
+	pub fn observation(&mut self) -> &[u8] {
+		if self.observation_type == ObservationType::Rgb {
+			return self.fetch_screen_rgb();
+		}
+		if self.observation_dirty {
+			let buf = self.observation_data.as_mut_ptr();
+			unsafe {
+				match self.observation_type {
+					ObservationType::Rgb => unreachable!(),
+					ObservationType::Grayscale => ale_sys::getScreenGrayscale(self.ptr, buf),
+					ObservationType::Ram => ale_sys::getRAM(self.ptr, buf),
+					ObservationType::Palette => ale_sys::getScreen(self.ptr, buf),
+				}
+			}
+			self.observation_dirty = false;
+		}
+		&self.observation_data
+	}
+
+	/// Resets the game, and returns the first observation of the new episode.
+	pub fn reset(&mut self) -> &[u8] {
+		self.reset_game();
+		self.observation()
+	}
+
+	/// Same as [`Ale::step`], but also returns the observation after the action.
+	pub fn step_observe(&mut self, action: u8) -> Result<(StepResult, &[u8])> {
+		let result = self.step(action)?;
+		Ok((result, self.observation()))
+	}
+
+	/// Returns a copy of the cached RGB screen. Prefer [`Ale::screen_rgb`], which does not allocate.
 	pub fn screen(&self) -> Vec<u8> {
 		self.screen_data.clone()
 	}
 
+	/// Returns the cached RGB screen, which is `screen_height() * screen_width() * 3` bytes long.
+	///
+	/// The screen is only kept up to date after every action when observations are RGB and lazy screen fetching is
+	/// disabled, so this may be out of date otherwise. Use [`Ale::fetch_screen_rgb`] in that case.
+	pub fn screen_rgb(&self) -> &[u8] {
+		&self.screen_data
+	}
+
+	/// Returns the RGB screen, reading it from the emulator first if it has changed since it was last read.
+	pub fn fetch_screen_rgb(&mut self) -> &[u8] {
+		if self.screen_dirty {
+			self.read_screen();
+		}
+		&self.screen_data
+	}
+
+	/// Returns whether the screen is only read from the emulator when requested.
+	pub fn lazy_screen(&self) -> bool {
+		self.lazy_screen
+	}
+
+	/// Sets whether the screen should only be read from the emulator when requested through
+	/// [`Ale::fetch_screen_rgb`], instead of after every action.
+	///
+	/// This saves a copy of the screen on every action, which is worthwhile when not every frame is observed. The RGB
+	/// screen is only read after every action when the observation type is [`ObservationType::Rgb`], so this has no
+	/// effect for other observation types, which are always read when requested.
+	pub fn set_lazy_screen(&mut self, lazy: bool) {
+		self.lazy_screen = lazy;
+		if self.screen_dirty && self.eager_screen() {
+			self.read_screen();
+		}
+	}
+
+	/// Whether the RGB screen is read after every action, rather than when it is requested.
+	fn eager_screen(&self) -> bool {
+		!self.lazy_screen && self.observation_type == ObservationType::Rgb
+	}
+
+	fn screen_changed(&mut self) {
+		self.observation_dirty = true;
+		if self.eager_screen() {
+			self.read_screen();
+		} else {
+			self.screen_dirty = true;
+		}
+	}
+
+	fn read_screen(&mut self) {
+		unsafe {
+			ale_sys::getScreenRGB(self.ptr, self.screen_data.as_mut_ptr());
+		}
+		self.screen_dirty = false;
+	}
+
 	/// Indicates if the game has ended.
 	pub fn is_game_over(&mut self) -> bool {
 		unsafe { ale_sys::game_over(self.ptr) }
 	}
 
-		// pub fn getString(ale: *mut ALEInterface, key: *const c_char) -> *const c_char; // TODO
+	/// Sets the value of a known setting.
+	///
+	/// # Examples
+	/// ```
+	/// # use ale::{Ale, Setting};
+	/// let mut ale = Ale::new(108_000);
+	/// ale.set(Setting::FrameSkip(4)).unwrap();
+	/// ```
+	pub fn set(&mut self, setting: Setting) -> Result<()> {
+		let key = setting.key().name();
+		match setting {
+			Setting::RandomSeed(v)
+			| Setting::FrameSkip(v)
+			| Setting::MaxNumFrames(v)
+			| Setting::MaxNumFramesPerEpisode(v) => self.set_int(key, v),
+			Setting::RepeatActionProbability(v) => self.set_float(key, v),
+			Setting::ColorAveraging(v)
+			| Setting::TruncateOnLossOfLife(v)
+			| Setting::Sound(v)
+			| Setting::DisplayScreen(v) => self.set_bool(key, v),
+			Setting::RecordScreenDir(v) | Setting::RecordSoundFilename(v) => self.set_string(key, &v),
+		}
+	}
+
+	/// Gets the value of a known setting.
+	pub fn get(&mut self, key: SettingKey) -> Result<Setting> {
+		use SettingKey::*;
+		let name = key.name();
+		Ok(match key {
+			RandomSeed => Setting::RandomSeed(self.get_int(name)?),
+			FrameSkip => Setting::FrameSkip(self.get_int(name)?),
+			RepeatActionProbability => Setting::RepeatActionProbability(self.get_float(name)?),
+			ColorAveraging => Setting::ColorAveraging(self.get_bool(name)?),
+			MaxNumFrames => Setting::MaxNumFrames(self.get_int(name)?),
+			MaxNumFramesPerEpisode => Setting::MaxNumFramesPerEpisode(self.get_int(name)?),
+			TruncateOnLossOfLife => Setting::TruncateOnLossOfLife(self.get_bool(name)?),
+			RecordScreenDir => Setting::RecordScreenDir(self.get_string(name)?),
+			RecordSoundFilename => Setting::RecordSoundFilename(self.get_string(name)?),
+			Sound => Setting::Sound(self.get_bool(name)?),
+			DisplayScreen => Setting::DisplayScreen(self.get_bool(name)?),
+		})
+	}
+
+	/// Returns the current value of every known setting, e.g. for logging the configuration of an experiment.
+	///
+	/// Each [`Setting`] is displayed as `key = value`.
+	pub fn dump_settings(&mut self) -> Result<Vec<Setting>> {
+		SettingKey::ALL.iter().map(|&key| self.get(key)).collect()
+	}
+
+	/// Gets the value of a setting as a string, whatever its type.
+	///
+	/// The ALE's C API does not define who owns the string it returns. Depending on the version of the ALE, it is
+	/// either a buffer that is shared by every instance and overwritten by the next call, or a copy that is never freed,
+	/// so every call leaks the length of the value. Versions whose `getString` returns `c_str()` of a temporary
+	/// `std::string` cannot be called soundly at all, so check the version of the ALE this crate is built against before
+	/// relying on this.
+	pub fn get_string(&mut self, key: &str) -> Result<String> {
+		// A shared buffer may be overwritten by another instance on another thread, so copy it out while holding a lock
+		static GET_STRING_LOCK: Mutex<()> = Mutex::new(());
+		let c_key = setting_key(key)?;
+		let _guard = GET_STRING_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+		let ptr = unsafe { ale_sys::getString(self.ptr, c_key.as_ptr()) };
+		if ptr.is_null() {
+			return Err(AleError::InvalidSettingKey(key.to_owned()));
+		}
+		Ok(unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+	}
 
 	// Gets the value of an integer setting.
-	pub fn get_int(&mut self, key: &str) -> i32 {
-		let c_key = CString::new(key).unwrap();
-		unsafe { ale_sys::getInt(self.ptr, c_key.as_ptr()) }
+	pub fn get_int(&mut self, key: &str) -> Result<i32> {
+		let c_key = setting_key(key)?;
+		Ok(unsafe { ale_sys::getInt(self.ptr, c_key.as_ptr()) })
 	}
 
 	// Gets the value of a bool setting.
-	pub fn get_bool(&mut self, key: &str) -> bool {
-		let c_key = CString::new(key).unwrap();
-		unsafe { ale_sys::getBool(self.ptr, c_key.as_ptr()) }
+	pub fn get_bool(&mut self, key: &str) -> Result<bool> {
+		let c_key = setting_key(key)?;
+		Ok(unsafe { ale_sys::getBool(self.ptr, c_key.as_ptr()) })
 	}
 
 	// Gets the value of a float setting.
-	pub fn get_float(&mut self, key: &str) -> f32 {
-		let c_key = CString::new(key).unwrap();
-		unsafe { ale_sys::getFloat(self.ptr, c_key.as_ptr()) }
+	pub fn get_float(&mut self, key: &str) -> Result<f32> {
+		let c_key = setting_key(key)?;
+		Ok(unsafe { ale_sys::getFloat(self.ptr, c_key.as_ptr()) })
 	}
 
 	// Sets the value of a string setting.
-	pub fn set_string(&mut self, key: &str, value: &str) {
-		let c_key = CString::new(key).unwrap();
-		let c_value = CString::new(value).unwrap();
+	pub fn set_string(&mut self, key: &str, value: &str) -> Result<()> {
+		let c_key = setting_key(key)?;
+		let c_value = CString::new(value)
+			.map_err(|_| AleError::InvalidSettingValue { key: key.to_owned(), value: value.to_owned() })?;
 		unsafe {
 			ale_sys::setString(self.ptr, c_key.as_ptr(), c_value.as_ptr());
 		}
+		Ok(())
 	}
 
 	// Sets the value of a bool setting.
-	pub fn set_bool(&mut self, key: &str, value: bool) {
-		let c_key = CString::new(key).unwrap();
+	pub fn set_bool(&mut self, key: &str, value: bool) -> Result<()> {
+		let c_key = setting_key(key)?;
 		unsafe {
 			ale_sys::setBool(self.ptr, c_key.as_ptr(), value);
 		}
+		Ok(())
 	}
 
 	// Sets the value of an integer setting.
-	pub fn set_int(&mut self, key: &str, value: i32) {
-		let c_key = CString::new(key).unwrap();
+	pub fn set_int(&mut self, key: &str, value: i32) -> Result<()> {
+		let c_key = setting_key(key)?;
 		unsafe {
 			ale_sys::setInt(self.ptr, c_key.as_ptr(), value);
 		}
+		Ok(())
 	}
 
 	// Sets the value of a float setting.
-	pub fn set_float(&mut self, key: &str, value: f32) {
-		let c_key = CString::new(key).unwrap();
+	pub fn set_float(&mut self, key: &str, value: f32) -> Result<()> {
+		let c_key = setting_key(key)?;
 		unsafe {
 			ale_sys::setFloat(self.ptr, c_key.as_ptr(), value);
 		}
+		Ok(())
 	}
 
 	/// Resets the Atari and loads a bundled game.
@@ -159,63 +466,138 @@ impl Ale {
 	/// After this call the game should be ready to play. This is necessary after changing a
 	/// setting for the setting to take effect.
 	///
-	/// Returns an error if there was an IO exception when saving the bundled ROM to a temporary directory.
+	/// The ROM is written to a per-user cache directory the first time it is loaded, and the same file is reused
+	/// afterwards, see [`Ale::load_rom_bytes`].
+	///
+	/// Returns an error if the ROM was not compiled in (see [`BundledRom::data`]), if there was an IO exception when
+	/// saving the bundled ROM to the cache directory, or if the ALE failed to load it.
 	///
 	/// # Examples
 	/// ```
 	/// # use ale::{Ale, BundledRom};
-	/// let mut ale = Ale::new();
-	/// ale.load_rom(BundledRom::Breakout);
-	/// ale.act(1);
+	/// let mut ale = Ale::new(108_000);
+	/// ale.load_rom(BundledRom::Breakout).unwrap();
+	/// ale.act(1).unwrap();
 	/// assert_eq!(ale.is_game_over(), false);
 	/// ```
-	pub fn load_rom(&mut self, rom: BundledRom) -> io::Result<()> {
-		// Save ROM to temp dir
-		let dir = tempdir::TempDir::new("ale-rs")?;
-		let rom_path = dir.path().join(rom.filename());
-		std::fs::write(&rom_path, rom.data())?;
-
-		// Call load_rom_file
-		let rom_path_string = rom_path.to_string_lossy().to_string();
-		let rom_path_c_str = CString::new(rom_path_string).expect("Invalid path");
-		self.load_rom_file(&rom_path_c_str);
-		Ok(())
+	pub fn load_rom(&mut self, rom: BundledRom) -> Result<()> {
+		self.load_rom_bytes(rom.filename(), &rom.data()?)
+	}
+
+	/// Resets the Atari and loads a game from memory.
+	///
+	/// The ALE picks the settings for the game based on its file name, so `name` should be the name the ROM is
+	/// expected to have, e.g. `"breakout.bin"`.
+	///
+	/// The ROM is written to a cache directory that persists between processes, named after its MD5 checksum, so each
+	/// distinct ROM is only written once. The directory is [`ROM_CACHE_DIR_ENV`](crate::ROM_CACHE_DIR_ENV) if set,
+	/// otherwise `ale-rs/roms` in the user's cache directory (`$XDG_CACHE_HOME` or `~/.cache`).
+	///
+	/// Returns [`AleError::RomLoad`] if `name` is not a plain file name, or if the ALE failed to load the ROM.
+	pub fn load_rom_bytes(&mut self, name: &str, data: &[u8]) -> Result<()> {
+		let path = crate::rom_cache::rom_file(name, data)?;
+		self.load_rom_path(path)
 	}
 
 	/// Resets the Atari and loads a game from the file specified.
 	///
 	/// After this call the game should be ready to play. This is necessary after changing a
 	/// setting for the setting to take effect.
-	pub fn load_rom_file(&mut self, rom_file: &CStr) {
+	///
+	/// The action set used by [`Ale::act`] is recomputed for the new game.
+	///
+	/// Returns [`AleError::RomLoad`] if the file cannot be read, or if the ALE did not recognise it as a game.
+	///
+	/// The C API does not tell which game is loaded, so a rejected ROM is only detected when no game is left loaded
+	/// afterwards. If a game was already loaded, the ALE may keep it running after rejecting the new ROM, which is
+	/// then reported as a success.
+	pub fn load_rom_path(&mut self, path: impl AsRef<Path>) -> Result<()> {
+		let path = path.as_ref();
+		let rom_load_error = |reason: &str| AleError::RomLoad { path: path.to_path_buf(), reason: reason.to_owned() };
+		match std::fs::metadata(path) {
+			Ok(metadata) if !metadata.is_file() => return Err(rom_load_error("not a file")),
+			Ok(metadata) if metadata.len() == 0 => return Err(rom_load_error("file is empty")),
+			Ok(_) => {}
+			Err(e) => return Err(rom_load_error(&e.to_string())),
+		}
+		#[cfg(unix)]
+		let path_bytes = path.as_os_str().as_bytes();
+		#[cfg(not(unix))]
+		let path_bytes = path.to_str().ok_or_else(|| rom_load_error("path is not valid UTF-8"))?;
+		let c_path = CString::new(path_bytes).map_err(|_| rom_load_error("path contains a nul byte"))?;
+		let data = std::fs::read(path).map_err(|e| rom_load_error(&e.to_string()))?;
+		self.rom_md5 = None;
+		self.rom_path = None;
 		unsafe {
-			ale_sys::loadROM(self.ptr, rom_file.as_ptr());
+			ale_sys::loadROM(self.ptr, c_path.as_ptr());
 		}
+		if unsafe { ale_sys::getMinimalActionSize(self.ptr) } <= 0 {
+			return Err(rom_load_error("the ALE did not load a game"));
+		}
+		self.rom_md5 = Some(md5::compute(&data).0);
+		self.rom_path = Some(path.to_path_buf());
+		self.mode = None;
+		self.difficulty = None;
+		// Loading a ROM resets the emulator, so the tracked episode and cached screen belong to the previous game
+		self.score = 0;
+		self.lives = self.current_lives() as u8;
+		self.screen_changed();
+		self.refresh_action_set()
+	}
+
+	/// Returns the MD5 checksum of the loaded ROM, as a lowercase hex string, or `None` if no ROM is loaded.
+	pub fn rom_md5(&self) -> Option<String> {
+		self.rom_md5.map(|md5| format!("{:x}", md5::Digest(md5)))
+	}
+
+	/// Resets the Atari and loads a game from the file specified.
+	///
+	/// Same as [`Ale::load_rom_path`], which should be preferred.
+	///
+	/// Returns [`AleError::RomLoad`] if the path is not valid UTF-8 on platforms other than Unix.
+	pub fn load_rom_file(&mut self, rom_file: &CStr) -> Result<()> {
+		#[cfg(unix)]
+		let path = Path::new(OsStr::from_bytes(rom_file.to_bytes()));
+		#[cfg(not(unix))]
+		let path = Path::new(rom_file.to_str().map_err(|_| AleError::RomLoad {
+			path: rom_file.to_string_lossy().into_owned().into(),
+			reason: "path is not valid UTF-8".into(),
+		})?);
+		self.load_rom_path(path)
 	}
 	
 	/// Returns the vector of modes available for the current game.
 	///
 	/// This should be called only after the rom is loaded.
-	pub fn available_modes(&mut self) -> Vec<i32> {
-		let size = unsafe { ale_sys::getAvailableModesSize(self.ptr) };
-		assert!(size >= 0);
-		let mut available_modes = vec![0; size as usize];
+	pub fn available_modes(&mut self) -> Result<Vec<i32>> {
+		let size = to_size(unsafe { ale_sys::getAvailableModesSize(self.ptr) })?;
+		let mut available_modes = vec![0; size];
 		unsafe {
 			ale_sys::getAvailableModes(self.ptr, available_modes.as_mut_ptr());
 		}
-		return available_modes;
+		Ok(available_modes)
 	}
 
 	/// Sets the mode of the game.
 	///
 	/// This should be called only after the rom is loaded.
 	///
-	/// # Panics
-	/// If the mode is invalid.
-	pub fn set_mode(&mut self, mode: i32) {
-		assert!(self.available_modes().contains(&mode), "Invalid mode: {}", mode);
+	/// Returns [`AleError::InvalidMode`] if the mode is not one of [`Ale::available_modes`].
+	pub fn set_mode(&mut self, mode: i32) -> Result<()> {
+		let available = self.available_modes()?;
+		if !available.contains(&mode) {
+			return Err(AleError::InvalidMode { mode, available });
+		}
 		unsafe {
 			ale_sys::setMode(self.ptr, mode);
 		}
+		self.mode = Some(mode);
+		Ok(())
+	}
+
+	/// Returns the mode set with [`Ale::set_mode`] since the ROM was loaded, or `None` if the default mode is used.
+	pub fn mode(&self) -> Option<i32> {
+		self.mode
 	}
 
 	/// Returns the vector of difficulties available for the current game.
@@ -230,49 +612,56 @@ impl Ale {
 	/// | 2           | left B/right A |
 	/// | 3           | left A/right B |
 	/// | 4           | left A/right A |
-	pub fn available_difficulties(&mut self) -> Vec<i32> {
-		let size = unsafe { ale_sys::getAvailableDifficultiesSize(self.ptr) };
-		assert!(size >= 0);
-		let mut available_difficulties = vec![0; size as usize];
+	pub fn available_difficulties(&mut self) -> Result<Vec<i32>> {
+		let size = to_size(unsafe { ale_sys::getAvailableDifficultiesSize(self.ptr) })?;
+		let mut available_difficulties = vec![0; size];
 		unsafe {
 			ale_sys::getAvailableDifficulties(self.ptr, available_difficulties.as_mut_ptr());
 		}
-		return available_difficulties;
+		Ok(available_difficulties)
 	}
 
 	/// Sets the difficulty of the game.
 	///
 	/// This should be called only after the rom is loaded.
 	///
-	/// # Panics
-	/// If the difficulty is not a valid difficulty
-	pub fn set_difficulty(&mut self, difficulty: i32) {
-		assert!(self.available_difficulties().contains(&difficulty), "Invalid difficulty: {}", difficulty);
+	/// Returns [`AleError::InvalidDifficulty`] if the difficulty is not one of [`Ale::available_difficulties`].
+	pub fn set_difficulty(&mut self, difficulty: i32) -> Result<()> {
+		let available = self.available_difficulties()?;
+		if !available.contains(&difficulty) {
+			return Err(AleError::InvalidDifficulty { difficulty, available });
+		}
 		unsafe {
 			ale_sys::setDifficulty(self.ptr, difficulty);
 		}
+		self.difficulty = Some(difficulty);
+		Ok(())
+	}
+
+	/// Returns the difficulty set with [`Ale::set_difficulty`] since the ROM was loaded, or `None` if the default
+	/// difficulty is used.
+	pub fn difficulty(&self) -> Option<i32> {
+		self.difficulty
 	}
 
 	/// Returns the vector of legal actions. This should be called only after the ROM is loaded.
-	pub fn legal_action_set(&mut self) -> Vec<i32> {
-		let size = unsafe { ale_sys::getLegalActionSize(self.ptr) };
-		assert!(size >= 0);
-		let mut legal_actions = vec![0; size as usize];
+	pub fn legal_action_set(&mut self) -> Result<Vec<i32>> {
+		let size = to_size(unsafe { ale_sys::getLegalActionSize(self.ptr) })?;
+		let mut legal_actions = vec![0; size];
 		unsafe {
 			ale_sys::getLegalActionSet(self.ptr, legal_actions.as_mut_ptr());
 		}
-		return legal_actions;
+		Ok(legal_actions)
 	}
 
 	/// Returns the vector of the minimal set of actions needed to play the game.
-	pub fn minimal_action_set(&mut self) -> Vec<i32> {
-		let size = unsafe { ale_sys::getMinimalActionSize(self.ptr) };
-		assert!(size >= 0);
-		let mut minimal_actions = vec![0; size as usize];
+	pub fn minimal_action_set(&mut self) -> Result<Vec<i32>> {
+		let size = to_size(unsafe { ale_sys::getMinimalActionSize(self.ptr) })?;
+		let mut minimal_actions = vec![0; size];
 		unsafe {
 			ale_sys::getMinimalActionSet(self.ptr, minimal_actions.as_mut_ptr());
 		}
-		return minimal_actions;
+		Ok(minimal_actions)
 	}
 
 	/// Returns the frame number since the loading of the ROM.
@@ -292,54 +681,72 @@ impl Ale {
 
 	/// Writes the emulator's RAM contents to the buffer provided.
 	///
-	/// # Panics
-	/// If the buffer is smaller than what [`Ale::ram_size()`] returns.
-	pub fn get_ram(&mut self, ram: &mut [u8]) {
-		assert!(ram.len() >= self.ram_size());
+	/// Returns [`AleError::BufferTooSmall`] if the buffer is smaller than what [`Ale::ram_size()`] returns.
+	pub fn get_ram(&mut self, ram: &mut [u8]) -> Result<()> {
+		check_buffer(ram, self.ram_size()?)?;
 		unsafe {
 			ale_sys::getRAM(self.ptr, ram.as_mut_ptr());
 		}
+		Ok(())
 	}
 
 	/// Get the size of the emulator's RAM, in bytes.
-	pub fn ram_size(&mut self) -> usize {
-		unsafe { ale_sys::getRAMSize(self.ptr) }.try_into().expect("invalid size")
+	pub fn ram_size(&mut self) -> Result<usize> {
+		to_size(unsafe { ale_sys::getRAMSize(self.ptr) })
 	}
 
 	/// Get the scren's width in pixels.
-	pub fn screen_width(&mut self) -> usize {
-		unsafe { ale_sys::getScreenWidth(self.ptr) }.try_into().expect("invalid size")
+	pub fn screen_width(&mut self) -> Result<usize> {
+		to_size(unsafe { ale_sys::getScreenWidth(self.ptr) })
 	}
 
 	/// Get the scren's height in pixels.
-	pub fn screen_height(&mut self) -> usize {
-		unsafe { ale_sys::getScreenHeight(self.ptr) }.try_into().expect("invalid size")
+	pub fn screen_height(&mut self) -> Result<usize> {
+		to_size(unsafe { ale_sys::getScreenHeight(self.ptr) })
+	}
+
+	/// Number of pixels on the screen, using the dimensions cached when the emulator was created.
+	fn screen_pixels(&self) -> usize {
+		self.screen_size.0 as usize * self.screen_size.1 as usize
 	}
 
 	/// Writes the screen's data to the buffer provided, in RGB format.
 	///
 	/// Pixel value at `x,y` is equal to `scren_data[y * screen_width() + x]`.
 	///
-	/// # Panics
-	/// If the buffer is smaller than `screen_width() * screen_height() * 3`.
-	pub fn get_screen_rgb(&mut self, screen_data: &mut [u8]) {
-		assert!(screen_data.len() >= self.screen_width() * self.screen_height() * 3);
+	/// Returns [`AleError::BufferTooSmall`] if the buffer is smaller than `screen_width() * screen_height() * 3`.
+	pub fn get_screen_rgb(&mut self, screen_data: &mut [u8]) -> Result<()> {
+		check_buffer(screen_data, self.screen_pixels() * 3)?;
 		unsafe {
 			ale_sys::getScreenRGB(self.ptr, screen_data.as_mut_ptr());
 		}
+		Ok(())
+	}
+
+	/// Writes the screen's raw palette indices to the buffer provided.
+	///
+	/// Pixel value at `x,y` is equal to `scren_data[y * screen_width() + x]`.
+	///
+	/// Returns [`AleError::BufferTooSmall`] if the buffer is smaller than `screen_width() * screen_height()`.
+	pub fn get_screen_palette(&mut self, screen_data: &mut [u8]) -> Result<()> {
+		check_buffer(screen_data, self.screen_pixels())?;
+		unsafe {
+			ale_sys::getScreen(self.ptr, screen_data.as_mut_ptr());
+		}
+		Ok(())
 	}
 
 	/// Writes the screen's data to the buffer provided, in grayscale format, where `0 = black` and `255 = white`.
 	///
 	/// Pixel value at `x,y` is equal to `scren_data[y * screen_width() + x]`.
 	///
-	/// # Panics
-	/// If the buffer is smaller than `screen_width() * screen_height()`.
-	pub fn get_screen_grayscale(&mut self, screen_data: &mut [u8]) {
-		assert!(screen_data.len() >= self.screen_width() * self.screen_height());
+	/// Returns [`AleError::BufferTooSmall`] if the buffer is smaller than `screen_width() * screen_height()`.
+	pub fn get_screen_grayscale(&mut self, screen_data: &mut [u8]) -> Result<()> {
+		check_buffer(screen_data, self.screen_pixels())?;
 		unsafe {
 			ale_sys::getScreenGrayscale(self.ptr, screen_data.as_mut_ptr());
 		}
+		Ok(())
 	}
 
 	/// Save the state of the system, to be restored using [`Ale::load_state`].
@@ -358,29 +765,235 @@ impl Ale {
 
 	/// This makes a copy of the environment state. This copy does *not* include pseudorandomness, making it suitable for planning purposes. By contrast, see [`Ale::clone_system_state()`].
 	pub fn clone_state(&mut self) -> AleState {
-		AleState { ptr: unsafe { ale_sys::cloneState(self.ptr) } }
+		AleState { ptr: unsafe { ale_sys::cloneState(self.ptr) }, rom_md5: self.rom_md5, system: false }
 	}
 
 	/// Reverse operation of [`Ale::clone_state`]. This does not restore pseudorandomness, so that repeated
 	/// calls to [`Ale::restore_state`] in the stochastic controls setting will not lead to the same outcomes.
 	///
 	/// By contrast, see [`Ale::restore_system_state`].
-	pub fn restore_state(&mut self, state: &AleState) {
+	///
+	/// This only restores the emulator: the screen, score and lives tracked by this wrapper are left stale until the
+	/// next action. Use [`Ale::snapshot`] and [`Ale::restore`] to restore those as well.
+	///
+	/// Returns [`AleError::StateRomMismatch`] if the state was not cloned while the same ROM was loaded.
+	///
+	/// # Examples
+	/// ```
+	/// # use ale::{Ale, AleError, AleState, BundledRom};
+	/// let mut ale = Ale::new(108_000);
+	/// ale.load_rom(BundledRom::Breakout).unwrap();
+	/// let state = AleState::from_bytes(&ale.clone_state().to_bytes()).unwrap();
+	/// ale.load_rom(BundledRom::Pong).unwrap();
+	/// assert!(matches!(ale.restore_state(&state), Err(AleError::StateRomMismatch { .. })));
+	/// ale.load_rom(BundledRom::Breakout).unwrap();
+	/// ale.restore_state(&state).unwrap();
+	/// ```
+	pub fn restore_state(&mut self, state: &AleState) -> Result<()> {
+		self.check_state_rom(state)?;
 		unsafe {
 			ale_sys::restoreState(self.ptr, state.ptr);
 		}
+		Ok(())
 	}
 
 	/// This makes a copy of the system & environment state, suitable for serialization. This includes pseudorandomness and so is *not* suitable for planning purposes.
 	pub fn clone_system_state(&mut self) -> AleState {
-		AleState { ptr: unsafe { ale_sys::cloneSystemState(self.ptr) } }
+		AleState { ptr: unsafe { ale_sys::cloneSystemState(self.ptr) }, rom_md5: self.rom_md5, system: true }
 	}
 
 	/// Reverse operation of [`Ale::clone_system_state`].
-	pub fn restore_system_state(&mut self, state: &AleState) {
+	///
+	/// Returns [`AleError::StateRomMismatch`] if the state was not cloned while the same ROM was loaded.
+	pub fn restore_system_state(&mut self, state: &AleState) -> Result<()> {
+		self.check_state_rom(state)?;
 		unsafe {
 			ale_sys::restoreSystemState(self.ptr, state.ptr);
 		}
+		Ok(())
+	}
+
+	fn check_state_rom(&self, state: &AleState) -> Result<()> {
+		if self.rom_md5.is_none() || state.rom_md5 != self.rom_md5 {
+			return Err(AleError::StateRomMismatch { state: state.rom_md5(), loaded: self.rom_md5() });
+		}
+		Ok(())
+	}
+
+	/// Takes a snapshot of the environment, including the score and lives tracked by this wrapper. Like
+	/// [`Ale::clone_state`], this does *not* include pseudorandomness.
+	///
+	/// # Examples
+	/// ```
+	/// # use ale::{Ale, BundledRom};
+	/// let mut ale = Ale::new(108_000);
+	/// ale.load_rom(BundledRom::Breakout).unwrap();
+	/// let snapshot = ale.snapshot();
+	/// for _ in 0..100 {
+	///     ale.act(1).unwrap();
+	/// }
+	/// ale.restore(&snapshot).unwrap();
+	/// assert_eq!(ale.episode_frame_number(), 0);
+	/// assert_eq!(ale.current_lives(), snapshot.lives());
+	/// ```
+	pub fn snapshot(&mut self) -> EnvSnapshot {
+		EnvSnapshot { state: self.clone_state(), score: self.score, lives: self.lives }
+	}
+
+	/// Same as [`Ale::snapshot`], but includes pseudorandomness, like [`Ale::clone_system_state`].
+	pub fn system_snapshot(&mut self) -> EnvSnapshot {
+		EnvSnapshot { state: self.clone_system_state(), score: self.score, lives: self.lives }
+	}
+
+	/// Restores a snapshot taken by [`Ale::snapshot`] or [`Ale::system_snapshot`], along with the score and lives, and
+	/// re-reads the screen (or marks it as stale if the screen is lazy).
+	///
+	/// Returns [`AleError::StateRomMismatch`] if the snapshot was not taken while the same ROM was loaded, in which
+	/// case nothing is restored.
+	pub fn restore(&mut self, snapshot: &EnvSnapshot) -> Result<()> {
+		if snapshot.state.system {
+			self.restore_system_state(&snapshot.state)?;
+		} else {
+			self.restore_state(&snapshot.state)?;
+		}
+		self.score = snapshot.score;
+		self.lives = snapshot.lives;
+		self.screen_changed();
+		Ok(())
+	}
+
+	/// Sets the seed of the ALE's random number generator, and reloads the ROM for it to take effect, keeping the mode
+	/// and difficulty. This starts a new episode.
+	///
+	/// Returns [`AleError::NoRomLoaded`] if no ROM is loaded.
+	pub fn reseed(&mut self, seed: i32) -> Result<()> {
+		let rom_path = self.rom_path.clone().ok_or(AleError::NoRomLoaded)?;
+		let (mode, difficulty) = (self.mode, self.difficulty);
+		self.set(Setting::RandomSeed(seed))?;
+		self.load_rom_path(rom_path)?;
+		if let Some(mode) = mode {
+			self.set_mode(mode)?;
+		}
+		if let Some(difficulty) = difficulty {
+			self.set_difficulty(difficulty)?;
+		}
+		self.reset_game();
+		Ok(())
+	}
+
+	/// Creates an independent copy of this environment, with the same ROM, settings, mode, difficulty, action set,
+	/// observation type and emulator state, including pseudorandomness, score and lives.
+	///
+	/// The recording settings ([`Setting::RecordScreenDir`] and [`Setting::RecordSoundFilename`]) and
+	/// [`Setting::DisplayScreen`] are not copied, so the fork does not record or display anything.
+	///
+	/// The ROM is reloaded from the file it was loaded from. For bundled ROMs and [`Ale::load_rom_bytes`] this is the
+	/// copy in the cache directory, so nothing is written to disk.
+	///
+	/// Returns [`AleError::NoRomLoaded`] if no ROM is loaded, or [`AleError::RomLoad`] if the ROM file can no longer be
+	/// loaded or has changed since it was loaded.
+	///
+	/// # Examples
+	/// ```
+	/// # use ale::{Ale, BundledRom};
+	/// let mut ale = Ale::new(108_000);
+	/// ale.load_rom(BundledRom::Breakout).unwrap();
+	/// ale.act(1).unwrap();
+	/// let mut fork = ale.fork().unwrap();
+	/// assert_eq!(fork.frame_number(), ale.frame_number());
+	/// assert_eq!(fork.step(0).unwrap(), ale.step(0).unwrap());
+	/// ```
+	pub fn fork(&mut self) -> Result<Ale> {
+		let rom_path = self.rom_path.clone().ok_or(AleError::NoRomLoaded)?;
+		let mut fork = Ale::try_new(self.max_frames)?;
+		// Settings such as the seed and frame skip are only read when the ROM is loaded. Recording and displaying are
+		// left disabled, so that the fork does not overwrite the files of this environment or open another window.
+		for setting in self.dump_settings()? {
+			match setting.key() {
+				SettingKey::RecordScreenDir | SettingKey::RecordSoundFilename | SettingKey::DisplayScreen => {}
+				_ => fork.set(setting)?,
+			}
+		}
+		fork.load_rom_path(&rom_path)?;
+		if fork.rom_md5 != self.rom_md5 {
+			return Err(AleError::RomLoad { path: rom_path, reason: "the file changed since it was loaded".into() });
+		}
+		fork.set_action_set(self.action_set_kind)?;
+		fork.set_observation_type(self.observation_type)?;
+		fork.set_lazy_screen(self.lazy_screen);
+		if let Some(mode) = self.mode {
+			fork.set_mode(mode)?;
+		}
+		if let Some(difficulty) = self.difficulty {
+			fork.set_difficulty(difficulty)?;
+		}
+		fork.restore(&self.system_snapshot())?;
+		Ok(fork)
+	}
+
+	/// Saves a snapshot of the environment to a `.alestate` file, along with a header describing the loaded ROM, mode,
+	/// difficulty and progress of the episode. See [`StateFileHeader`] for the format.
+	///
+	/// Returns [`AleError::NoRomLoaded`] if no ROM is loaded.
+	///
+	/// # Examples
+	/// ```no_run
+	/// # use ale::{Ale, BundledRom, StateFileOptions};
+	/// let mut ale = Ale::new(108_000);
+	/// ale.load_rom(BundledRom::Breakout).unwrap();
+	/// ale.save_state_file("breakout.alestate", StateFileOptions::new()).unwrap();
+	/// let header = ale.load_state_file("breakout.alestate").unwrap();
+	/// assert_eq!(header.episode_frame_number, 0);
+	/// ```
+	pub fn save_state_file(&mut self, path: impl AsRef<Path>, options: StateFileOptions) -> Result<()> {
+		let rom_md5 = self.rom_md5.ok_or(AleError::NoRomLoaded)?;
+		let snapshot = if options.system { self.system_snapshot() } else { self.snapshot() };
+		let header = StateFileHeader {
+			version: StateFileHeader::VERSION,
+			rom_md5: format!("{:x}", md5::Digest(rom_md5)),
+			mode: self.mode,
+			difficulty: self.difficulty,
+			frame_number: self.frame_number(),
+			episode_frame_number: self.episode_frame_number(),
+			score: snapshot.score,
+			lives: snapshot.lives.into(),
+			system: options.system,
+			compressed: options.compress,
+		};
+		state_file::write(path.as_ref(), &header, rom_md5, &snapshot.state.to_bytes())
+	}
+
+	/// Restores a state saved by [`Ale::save_state_file`], along with the score and lives, and returns the file's
+	/// header.
+	///
+	/// The state is only restored if it is compatible with this emulator, i.e. the same ROM is loaded and the same mode
+	/// and difficulty are set. Otherwise [`AleError::StateRomMismatch`] or [`AleError::StateSettingMismatch`] is
+	/// returned. Returns [`AleError::StateDecode`] if the file is not a valid state file.
+	pub fn load_state_file(&mut self, path: impl AsRef<Path>) -> Result<StateFileHeader> {
+		let (header, state) = state_file::read(path.as_ref())?;
+		if Some(&header.rom_md5) != self.rom_md5().as_ref() {
+			return Err(AleError::StateRomMismatch { state: Some(header.rom_md5), loaded: self.rom_md5() });
+		}
+		if header.mode != self.mode {
+			return Err(AleError::StateSettingMismatch { setting: "mode", state: header.mode, loaded: self.mode });
+		}
+		if header.difficulty != self.difficulty {
+			return Err(AleError::StateSettingMismatch {
+				setting: "difficulty",
+				state: header.difficulty,
+				loaded: self.difficulty,
+			});
+		}
+		let state = AleState::decode_state(&state)?;
+		if state.system != header.system {
+			return Err(AleError::StateDecode("system flag of the header does not match the state".into()));
+		}
+		let lives = header
+			.lives
+			.try_into()
+			.map_err(|_| AleError::StateDecode(format!("invalid number of lives {}", header.lives)))?;
+		self.restore(&EnvSnapshot { state, score: header.score, lives })?;
+		Ok(header)
 	}
 
 	/// Save the current screen as a png file
@@ -410,57 +1023,14 @@ impl Drop for Ale {
 	}
 }
 
-/// State of the ALE
-///
-/// Used mainly by [`Ale::clone_state`] & [`Ale::restore_state`] to save the emulator's state, and restore it at a later point.
-pub struct AleState {
-	ptr: *mut ale_sys::ALEState,
+/// Converts a setting key into a C string.
+fn setting_key(key: &str) -> Result<CString> {
+	CString::new(key).map_err(|_| AleError::InvalidSettingKey(key.to_owned()))
 }
-impl AleState {
-	/// Encodes the state as a raw bytestream.
-	///
-	/// # Panics
-	/// If the length of `buf` is not large enough. Use [`AleState::encode_state_len`] to get the needed length.
-	pub fn encode_state(&self, buf: &mut [u8]) {
-		assert!(
-			buf.len() >= self.encode_state_len(),
-			"Buffer not long enough to store encoded state. Expected {}, got {}",
-			self.encode_state_len(),
-			buf.len()
-		);
-		unsafe {
-			ale_sys::encodeState(self.ptr, buf.as_mut_ptr() as *mut _, buf.len() as c_int);
-		}
-	}
 
-	/// Returns the length of the buffer needed to encode the state.
-	///
-	/// # Panics
-	/// If the C API returns a negative size.
-	pub fn encode_state_len(&self) -> usize {
-		let size = unsafe { ale_sys::encodeStateLen(self.ptr) };
-		assert!(size >= 0, "Invalid size: {}", size);
-		size as usize
-	}
-
-	/// Decode state from a raw bytestream.
-	///
-	/// # Panics
-	/// If the serialized length is too long to fit into a C integer.
-	pub fn decode_state(serialized: &[u8]) -> AleState {
-		let len: c_int = serialized.len().try_into().expect("Length too long");
-		// TODO: Exceptions
-		AleState { ptr: unsafe { ale_sys::decodeState(serialized.as_ptr() as *const _, len) } }
-	}
-}
-impl Drop for AleState {
-	fn drop(&mut self) {
-		unsafe {
-			let ptr = self.ptr;
-			self.ptr = std::ptr::null_mut();
-			ale_sys::deleteState(ptr);
-		}
-	}
+/// Converts a size returned by the C API into a `usize`.
+pub(crate) fn to_size(size: c_int) -> Result<usize> {
+	size.try_into().map_err(|_| AleError::InvalidSize(size.into()))
 }
 
 pub enum LoggerMode {
@@ -468,349 +1038,3 @@ pub enum LoggerMode {
 	Warning = 1,
 	Error = 2,
 }
-
-/// Enum of ROMs that come bundled with the libarary.
-///
-/// Note: Commented out ROMs are supported, but not bundled.
-pub enum BundledRom {
-	Adventure,
-	AirRaid,
-	Alien,
-	Amidar,
-	Assault,
-	Asterix,
-	Asteroids,
-	Atlantis,
-	Atlantis2,
-	Backgammon,
-	BankHeist,
-	BasicMath,
-	BattleZone,
-	BeamRider,
-	Berzerk,
-	Blackjack,
-	Bowling,
-	Boxing,
-	Breakout,
-	Carnival,
-	Casino,
-	Centipede,
-	ChopperCommand,
-	Combat,
-	CrazyClimber,
-	Crossbow,
-	Darkchambers,
-	Defender,
-	DemonAttack,
-	DonkeyKong,
-	DoubleDunk,
-	Earthworld,
-	ElevatorAction,
-	Enduro,
-	Entombed,
-	Et,
-	FishingDerby,
-	FlagCapture,
-	Freeway,
-	Frogger,
-	Frostbite,
-	Galaxian,
-	Gopher,
-	Gravitar,
-	Hangman,
-	HauntedHouse,
-	Hero,
-	HumanCannonball,
-	IceHockey,
-	Jamesbond,
-	JourneyEscape,
-	Joust,
-	Kaboom,
-	Kangaroo,
-	KeystoneKapers,
-	KingKong,
-	Klax,
-	Koolaid,
-	Krull,
-	KungFuMaster,
-	LaserGates,
-	LostLuggage,
-	MarioBros,
-	MazeCraze,
-	MiniatureGolf,
-	MontezumaRevenge,
-	MrDo,
-	MsPacman,
-	NameThisGame,
-	Othello,
-	Pacman,
-	Phoenix,
-	Pitfall,
-	Pitfall2,
-	Pong,
-	Pooyan,
-	PrivateEye,
-	Qbert,
-	Riverraid,
-	RoadRunner,
-	Robotank,
-	Seaquest,
-	SirLancelot,
-	Skiing,
-	Solaris,
-	SpaceInvaders,
-	SpaceWar,
-	StarGunner,
-	Superman,
-	Surround,
-	Tennis,
-	Tetris,
-	TicTacToe3d,
-	TimePilot,
-	Trondead,
-	Turmoil,
-	Tutankham,
-	UpNDown,
-	Venture,
-	VideoCheckers,
-	VideoChess,
-	VideoCube,
-	VideoPinball,
-	Warlords,
-	WizardOfWor,
-	WordZapper,
-	YarsRevenge,
-	Zaxxon,
-}
-impl BundledRom {
-	/// Returns the filename that the ROM should be named, in order for the ALE to pick up on it and
-	/// use the correct settings.
-	pub fn filename(&self) -> &'static str {
-		use BundledRom::*;
-		match self {
-			Adventure => "adventure.bin",
-			AirRaid => "air_raid.bin",
-			Alien => "alien.bin",
-			Amidar => "amidar.bin",
-			Assault => "assault.bin",
-			Asterix => "asterix.bin",
-			Asteroids => "asteroids.bin",
-			Atlantis => "atlantis.bin",
-			Atlantis2 => "atlantis2.bin",
-			Backgammon => "backgammon.bin",
-			BankHeist => "bank_heist.bin",
-			BasicMath => "basic_math.bin",
-			BattleZone => "battle_zone.bin",
-			BeamRider => "beam_rider.bin",
-			Berzerk => "berzerk.bin",
-			Blackjack => "blackjack.bin",
-			Bowling => "bowling.bin",
-			Boxing => "boxing.bin",
-			Breakout => "breakout.bin",
-			Carnival => "carnival.bin",
-			Casino => "casino.bin",
-			Centipede => "centipede.bin",
-			ChopperCommand => "chopper_command.bin",
-			Combat => "combat.bin",
-			CrazyClimber => "crazy_climber.bin",
-			Crossbow => "crossbow.bin",
-			Darkchambers => "darkchambers.bin",
-			Defender => "defender.bin",
-			DemonAttack => "demon_attack.bin",
-			DonkeyKong => "donkey_kong.bin",
-			DoubleDunk => "double_dunk.bin",
-			Earthworld => "earthworld.bin",
-			ElevatorAction => "elevator_action.bin",
-			Enduro => "enduro.bin",
-			Entombed => "entombed.bin",
-			Et => "et.bin",
-			FishingDerby => "fishing_derby.bin",
-			FlagCapture => "flag_capture.bin",
-			Freeway => "freeway.bin",
-			Frogger => "frogger.bin",
-			Frostbite => "frostbite.bin",
-			Galaxian => "galaxian.bin",
-			Gopher => "gopher.bin",
-			Gravitar => "gravitar.bin",
-			Hangman => "hangman.bin",
-			HauntedHouse => "haunted_house.bin",
-			Hero => "hero.bin",
-			HumanCannonball => "human_cannonball.bin",
-			IceHockey => "ice_hockey.bin",
-			Jamesbond => "jamesbond.bin",
-			JourneyEscape => "journey_escape.bin",
-			Joust => "joust.bin",
-			Kaboom => "kaboom.bin",
-			Kangaroo => "kangaroo.bin",
-			KeystoneKapers => "keystone_kapers.bin",
-			KingKong => "king_kong.bin",
-			Klax => "klax.bin",
-			Koolaid => "koolaid.bin",
-			Krull => "krull.bin",
-			KungFuMaster => "kung_fu_master.bin",
-			LaserGates => "laser_gates.bin",
-			LostLuggage => "lost_luggage.bin",
-			MarioBros => "mario_bros.bin",
-			MazeCraze => "maze_craze.bin",
-			MiniatureGolf => "miniature_golf.bin",
-			MontezumaRevenge => "montezuma_revenge.bin",
-			MrDo => "mr_do.bin",
-			MsPacman => "ms_pacman.bin",
-			NameThisGame => "name_this_game.bin",
-			Othello => "othello.bin",
-			Pacman => "pacman.bin",
-			Phoenix => "phoenix.bin",
-			Pitfall => "pitfall.bin",
-			Pitfall2 => "pitfall2.bin",
-			Pong => "pong.bin",
-			Pooyan => "pooyan.bin",
-			PrivateEye => "private_eye.bin",
-			Qbert => "qbert.bin",
-			Riverraid => "riverraid.bin",
-			RoadRunner => "road_runner.bin",
-			Robotank => "robotank.bin",
-			Seaquest => "seaquest.bin",
-			SirLancelot => "sir_lancelot.bin",
-			Skiing => "skiing.bin",
-			Solaris => "solaris.bin",
-			SpaceInvaders => "space_invaders.bin",
-			SpaceWar => "space_war.bin",
-			StarGunner => "star_gunner.bin",
-			Superman => "superman.bin",
-			Surround => "surround.bin",
-			Tennis => "tennis.bin",
-			Tetris => "tetris.bin",
-			TicTacToe3d => "tic_tac_toe_3d.bin",
-			TimePilot => "time_pilot.bin",
-			Trondead => "trondead.bin",
-			Turmoil => "turmoil.bin",
-			Tutankham => "tutankham.bin",
-			UpNDown => "up_n_down.bin",
-			Venture => "venture.bin",
-			VideoCheckers => "video_checkers.bin",
-			VideoChess => "video_chess.bin",
-			VideoCube => "video_cube.bin",
-			VideoPinball => "video_pinball.bin",
-			Warlords => "warlords.bin",
-			WizardOfWor => "wizard_of_wor.bin",
-			WordZapper => "word_zapper.bin",
-			YarsRevenge => "yars_revenge.bin",
-			Zaxxon => "zaxxon.bin",
-		}
-	}
-
-	/// Returns the raw binary data of the ROM.
-	pub fn data(&self) -> &'static [u8] {
-		use BundledRom::*;
-		match self {
-			Adventure => include_bytes!("../roms/adventure.bin"),
-			AirRaid => include_bytes!("../roms/air_raid.bin"),
-			Alien => include_bytes!("../roms/alien.bin"),
-			Amidar => include_bytes!("../roms/amidar.bin"),
-			Assault => include_bytes!("../roms/assault.bin"),
-			Asterix => include_bytes!("../roms/asterix.bin"),
-			Asteroids => include_bytes!("../roms/asteroids.bin"),
-			Atlantis => include_bytes!("../roms/atlantis.bin"),
-			Atlantis2 => include_bytes!("../roms/atlantis2.bin"),
-			Backgammon => include_bytes!("../roms/backgammon.bin"),
-			BankHeist => include_bytes!("../roms/bank_heist.bin"),
-			BasicMath => include_bytes!("../roms/basic_math.bin"),
-			BattleZone => include_bytes!("../roms/battle_zone.bin"),
-			BeamRider => include_bytes!("../roms/beam_rider.bin"),
-			Berzerk => include_bytes!("../roms/berzerk.bin"),
-			Blackjack => include_bytes!("../roms/blackjack.bin"),
-			Bowling => include_bytes!("../roms/bowling.bin"),
-			Boxing => include_bytes!("../roms/boxing.bin"),
-			Breakout => include_bytes!("../roms/breakout.bin"),
-			Carnival => include_bytes!("../roms/carnival.bin"),
-			Casino => include_bytes!("../roms/casino.bin"),
-			Centipede => include_bytes!("../roms/centipede.bin"),
-			ChopperCommand => include_bytes!("../roms/chopper_command.bin"),
-			Combat => include_bytes!("../roms/combat.bin"),
-			CrazyClimber => include_bytes!("../roms/crazy_climber.bin"),
-			Crossbow => include_bytes!("../roms/crossbow.bin"),
-			Darkchambers => include_bytes!("../roms/darkchambers.bin"),
-			Defender => include_bytes!("../roms/defender.bin"),
-			DemonAttack => include_bytes!("../roms/demon_attack.bin"),
-			DonkeyKong => include_bytes!("../roms/donkey_kong.bin"),
-			DoubleDunk => include_bytes!("../roms/double_dunk.bin"),
-			Earthworld => include_bytes!("../roms/earthworld.bin"),
-			ElevatorAction => include_bytes!("../roms/elevator_action.bin"),
-			Enduro => include_bytes!("../roms/enduro.bin"),
-			Entombed => include_bytes!("../roms/entombed.bin"),
-			Et => include_bytes!("../roms/et.bin"),
-			FishingDerby => include_bytes!("../roms/fishing_derby.bin"),
-			FlagCapture => include_bytes!("../roms/flag_capture.bin"),
-			Freeway => include_bytes!("../roms/freeway.bin"),
-			Frogger => include_bytes!("../roms/frogger.bin"),
-			Frostbite => include_bytes!("../roms/frostbite.bin"),
-			Galaxian => include_bytes!("../roms/galaxian.bin"),
-			Gopher => include_bytes!("../roms/gopher.bin"),
-			Gravitar => include_bytes!("../roms/gravitar.bin"),
-			Hangman => include_bytes!("../roms/hangman.bin"),
-			HauntedHouse => include_bytes!("../roms/haunted_house.bin"),
-			Hero => include_bytes!("../roms/hero.bin"),
-			HumanCannonball => include_bytes!("../roms/human_cannonball.bin"),
-			IceHockey => include_bytes!("../roms/ice_hockey.bin"),
-			Jamesbond => include_bytes!("../roms/jamesbond.bin"),
-			JourneyEscape => include_bytes!("../roms/journey_escape.bin"),
-			Joust => include_bytes!("../roms/joust.bin"),
-			Kaboom => include_bytes!("../roms/kaboom.bin"),
-			Kangaroo => include_bytes!("../roms/kangaroo.bin"),
-			KeystoneKapers => include_bytes!("../roms/keystone_kapers.bin"),
-			KingKong => include_bytes!("../roms/king_kong.bin"),
-			Klax => include_bytes!("../roms/klax.bin"),
-			Koolaid => include_bytes!("../roms/koolaid.bin"),
-			Krull => include_bytes!("../roms/krull.bin"),
-			KungFuMaster => include_bytes!("../roms/kung_fu_master.bin"),
-			LaserGates => include_bytes!("../roms/laser_gates.bin"),
-			LostLuggage => include_bytes!("../roms/lost_luggage.bin"),
-			MarioBros => include_bytes!("../roms/mario_bros.bin"),
-			MazeCraze => include_bytes!("../roms/maze_craze.bin"),
-			MiniatureGolf => include_bytes!("../roms/miniature_golf.bin"),
-			MontezumaRevenge => include_bytes!("../roms/montezuma_revenge.bin"),
-			MrDo => include_bytes!("../roms/mr_do.bin"),
-			MsPacman => include_bytes!("../roms/ms_pacman.bin"),
-			NameThisGame => include_bytes!("../roms/name_this_game.bin"),
-			Othello => include_bytes!("../roms/othello.bin"),
-			Pacman => include_bytes!("../roms/pacman.bin"),
-			Phoenix => include_bytes!("../roms/phoenix.bin"),
-			Pitfall => include_bytes!("../roms/pitfall.bin"),
-			Pitfall2 => include_bytes!("../roms/pitfall2.bin"),
-			Pong => include_bytes!("../roms/pong.bin"),
-			Pooyan => include_bytes!("../roms/pooyan.bin"),
-			PrivateEye => include_bytes!("../roms/private_eye.bin"),
-			Qbert => include_bytes!("../roms/qbert.bin"),
-			Riverraid => include_bytes!("../roms/riverraid.bin"),
-			RoadRunner => include_bytes!("../roms/road_runner.bin"),
-			Robotank => include_bytes!("../roms/robotank.bin"),
-			Seaquest => include_bytes!("../roms/seaquest.bin"),
-			SirLancelot => include_bytes!("../roms/sir_lancelot.bin"),
-			Skiing => include_bytes!("../roms/skiing.bin"),
-			Solaris => include_bytes!("../roms/solaris.bin"),
-			SpaceInvaders => include_bytes!("../roms/space_invaders.bin"),
-			SpaceWar => include_bytes!("../roms/space_war.bin"),
-			StarGunner => include_bytes!("../roms/star_gunner.bin"),
-			Superman => include_bytes!("../roms/superman.bin"),
-			Surround => include_bytes!("../roms/surround.bin"),
-			Tennis => include_bytes!("../roms/tennis.bin"),
-			Tetris => include_bytes!("../roms/tetris.bin"),
-			TicTacToe3d => include_bytes!("../roms/tic_tac_toe_3d.bin"),
-			TimePilot => include_bytes!("../roms/time_pilot.bin"),
-			Trondead => include_bytes!("../roms/trondead.bin"),
-			Turmoil => include_bytes!("../roms/turmoil.bin"),
-			Tutankham => include_bytes!("../roms/tutankham.bin"),
-			UpNDown => include_bytes!("../roms/up_n_down.bin"),
-			Venture => include_bytes!("../roms/venture.bin"),
-			VideoCheckers => include_bytes!("../roms/video_checkers.bin"),
-			VideoChess => include_bytes!("../roms/video_chess.bin"),
-			VideoCube => include_bytes!("../roms/video_cube.bin"),
-			VideoPinball => include_bytes!("../roms/video_pinball.bin"),
-			Warlords => include_bytes!("../roms/warlords.bin"),
-			WizardOfWor => include_bytes!("../roms/wizard_of_wor.bin"),
-			WordZapper => include_bytes!("../roms/word_zapper.bin"),
-			YarsRevenge => include_bytes!("../roms/yars_revenge.bin"),
-			Zaxxon => include_bytes!("../roms/zaxxon.bin"),
-		}
-	}
-}
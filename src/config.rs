@@ -0,0 +1,140 @@
+use crate::{ActionSet, Ale, BundledRom, ObservationType, Result, Setting};
+
+/// Builder for an [`Ale`] instance.
+///
+/// Settings that the ALE only reads when a ROM is loaded are applied before the ROM is loaded, and the mode and
+/// difficulty are validated against the loaded game afterwards.
+///
+/// # Examples
+/// ```
+/// # use ale::{ActionSet, AleConfig, BundledRom};
+/// let mut ale = AleConfig::new()
+///     .seed(42)
+///     .frame_skip(4)
+///     .repeat_action_probability(0.25)
+///     .action_set(ActionSet::Minimal)
+///     .build(BundledRom::Breakout)
+///     .unwrap();
+/// ale.act(1).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AleConfig {
+	pub(crate) seed: Option<i32>,
+	frame_skip: Option<i32>,
+	repeat_action_probability: Option<f32>,
+	truncate_episodes_after: u32,
+	mode: Option<i32>,
+	difficulty: Option<i32>,
+	action_set: ActionSet,
+	lazy_screen: bool,
+	observation_type: ObservationType,
+}
+impl AleConfig {
+	/// Creates a new configuration, which leaves every ALE setting at its default value.
+	pub fn new() -> AleConfig {
+		AleConfig {
+			seed: None,
+			frame_skip: None,
+			repeat_action_probability: None,
+			truncate_episodes_after: 108_000,
+			mode: None,
+			difficulty: None,
+			action_set: ActionSet::default(),
+			lazy_screen: false,
+			observation_type: ObservationType::default(),
+		}
+	}
+
+	/// Sets the seed of the ALE's random number generator (`random_seed`).
+	pub fn seed(mut self, seed: i32) -> AleConfig {
+		self.seed = Some(seed);
+		self
+	}
+
+	/// Sets the number of frames each action is repeated for (`frame_skip`).
+	pub fn frame_skip(mut self, frame_skip: i32) -> AleConfig {
+		self.frame_skip = Some(frame_skip);
+		self
+	}
+
+	/// Sets the probability of the previous action being repeated instead of the chosen one, i.e. sticky actions
+	/// (`repeat_action_probability`).
+	pub fn repeat_action_probability(mut self, probability: f32) -> AleConfig {
+		self.repeat_action_probability = Some(probability);
+		self
+	}
+
+	/// Sets the number of frames after which [`Ale::step`] reports an episode as truncated, through
+	/// [`StepResult::truncated`](crate::StepResult::truncated).
+	///
+	/// This is tracked by [`Ale`] itself, and does not set the ALE's `max_num_frames_per_episode` setting, which ends
+	/// the game instead. Defaults to `108_000`, i.e. 30 minutes of play at 60 frames per second.
+	pub fn truncate_episodes_after(mut self, frames: u32) -> AleConfig {
+		self.truncate_episodes_after = frames;
+		self
+	}
+
+	/// Sets the game mode. This must be one of [`Ale::available_modes`] for the ROM being loaded.
+	pub fn mode(mut self, mode: i32) -> AleConfig {
+		self.mode = Some(mode);
+		self
+	}
+
+	/// Sets the game difficulty. This must be one of [`Ale::available_difficulties`] for the ROM being loaded.
+	pub fn difficulty(mut self, difficulty: i32) -> AleConfig {
+		self.difficulty = Some(difficulty);
+		self
+	}
+
+	/// Sets the action set that [`Ale::act`] indexes into.
+	pub fn action_set(mut self, action_set: ActionSet) -> AleConfig {
+		self.action_set = action_set;
+		self
+	}
+
+	/// Sets whether the screen is only read from the emulator when requested. See [`Ale::set_lazy_screen`].
+	pub fn lazy_screen(mut self, lazy: bool) -> AleConfig {
+		self.lazy_screen = lazy;
+		self
+	}
+
+	/// Sets the kind of observation returned by [`Ale::observation`].
+	pub fn observation_type(mut self, observation_type: ObservationType) -> AleConfig {
+		self.observation_type = observation_type;
+		self
+	}
+
+	/// Creates a new emulator instance with this configuration and loads `rom` into it.
+	///
+	/// Returns an error if the ROM could not be loaded, or if the mode or difficulty is not available for it.
+	pub fn build(&self, rom: BundledRom) -> Result<Ale> {
+		let mut ale = Ale::try_new(self.truncate_episodes_after)?;
+		ale.set_lazy_screen(self.lazy_screen);
+		if let Some(seed) = self.seed {
+			ale.set(Setting::RandomSeed(seed))?;
+		}
+		if let Some(frame_skip) = self.frame_skip {
+			ale.set(Setting::FrameSkip(frame_skip))?;
+		}
+		if let Some(probability) = self.repeat_action_probability {
+			ale.set(Setting::RepeatActionProbability(probability))?;
+		}
+		ale.load_rom(rom)?;
+		ale.set_action_set(self.action_set)?;
+		ale.set_observation_type(self.observation_type)?;
+		if let Some(mode) = self.mode {
+			ale.set_mode(mode)?;
+		}
+		if let Some(difficulty) = self.difficulty {
+			ale.set_difficulty(difficulty)?;
+		}
+		if self.mode.is_some() || self.difficulty.is_some() {
+			// The mode and difficulty only take effect after a reset
+			ale.reset_game();
+		}
+		Ok(ale)
+	}
+}
+impl Default for AleConfig {
+	fn default() -> AleConfig { AleConfig::new() }
+}
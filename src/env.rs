@@ -0,0 +1,190 @@
+use crate::{Action, Ale, Result, Space, StepResult};
+
+/// Information returned along with the first observation of an episode by [`Environment::reset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResetInfo {
+	/// Number of lives at the start of the episode.
+	pub lives: i32,
+	/// Frame number since the start of the episode.
+	pub episode_frame_number: i32,
+	/// Frame number since the loading of the ROM.
+	pub frame_number: i32,
+}
+impl From<&StepResult> for ResetInfo {
+	/// Takes the lives and frame numbers after a step, e.g. for wrappers that take steps while resetting.
+	fn from(result: &StepResult) -> ResetInfo {
+		ResetInfo {
+			lives: result.lives,
+			episode_frame_number: result.episode_frame_number,
+			frame_number: result.frame_number,
+		}
+	}
+}
+
+/// A Gymnasium-style environment with discrete actions, implemented by [`Ale`] and by the wrappers around it.
+///
+/// # Examples
+/// ```
+/// # use ale::{Ale, AleConfig, BundledRom, Environment};
+/// fn run_episode(env: &mut impl Environment) -> ale::Result<i32> {
+///     env.reset(Some(42))?;
+///     let mut score = 0;
+///     loop {
+///         let result = env.step(0)?;
+///         score += result.reward;
+///         if result.done() {
+///             return Ok(score);
+///         }
+///     }
+/// }
+/// let mut ale = AleConfig::new().truncate_episodes_after(1_000).build(BundledRom::Breakout).unwrap();
+/// run_episode(&mut ale).unwrap();
+/// ```
+pub trait Environment {
+	/// Type of the observations.
+	type Observation;
+
+	/// Starts a new episode, and returns its first observation.
+	///
+	/// If `seed` is given, the environment's random number generator is reseeded first, so that the episodes that
+	/// follow are reproducible.
+	fn reset(&mut self, seed: Option<i32>) -> Result<(Self::Observation, ResetInfo)>;
+
+	/// Starts a new episode like [`Environment::reset`], but writes its first observation into `out`.
+	///
+	/// The default implementation copies the observation returned by [`Environment::reset`]. Environments that can
+	/// write their observation without allocating it override this, like [`Ale`] does.
+	///
+	/// # Panics
+	/// Panics if the length of `out` is not that of the observation.
+	fn reset_into(&mut self, seed: Option<i32>, out: &mut [u8]) -> Result<ResetInfo>
+	where
+		Self::Observation: AsRef<[u8]>,
+	{
+		let (observation, info) = self.reset(seed)?;
+		out.copy_from_slice(observation.as_ref());
+		Ok(info)
+	}
+
+	/// Applies an action, which must be an element of [`Environment::action_space`].
+	///
+	/// The observation after the action is returned by [`Environment::observation`].
+	fn step(&mut self, action: u8) -> Result<StepResult>;
+
+	/// Returns the current observation, which is an element of [`Environment::observation_space`].
+	fn observation(&mut self) -> Self::Observation;
+
+	/// Writes the current observation into `out`.
+	///
+	/// The default implementation copies [`Environment::observation`], and is overridden like
+	/// [`Environment::reset_into`].
+	///
+	/// # Panics
+	/// Panics if the length of `out` is not that of the observation.
+	fn observation_into(&mut self, out: &mut [u8])
+	where
+		Self::Observation: AsRef<[u8]>,
+	{
+		out.copy_from_slice(self.observation().as_ref())
+	}
+
+	/// Returns the space of the observations.
+	fn observation_space(&self) -> Space;
+
+	/// Returns the space of the actions, which is always [`Space::Discrete`].
+	fn action_space(&self) -> Space;
+
+	/// Returns the Atari action that each action of [`Environment::action_space`] stands for, or `None` if that is not
+	/// known.
+	///
+	/// Wrappers that take actions on their own, like [`FireReset`](crate::wrappers::FireReset), use this to check that
+	/// the actions they take mean what they expect.
+	fn action_meanings(&self) -> Option<&[Action]> { None }
+}
+
+impl Environment for Ale {
+	type Observation = Vec<u8>;
+
+	/// Resets the game, reseeding it first with [`Ale::reseed`] if `seed` is given.
+	fn reset(&mut self, seed: Option<i32>) -> Result<(Vec<u8>, ResetInfo)> {
+		let info = reset_game(self, seed)?;
+		Ok((Environment::observation(self), info))
+	}
+
+	/// Resets the game like [`Environment::reset`], which only allocates if `seed` is given, as reseeding reloads the
+	/// ROM.
+	fn reset_into(&mut self, seed: Option<i32>, out: &mut [u8]) -> Result<ResetInfo> {
+		let info = reset_game(self, seed)?;
+		self.observation_into(out);
+		Ok(info)
+	}
+
+	fn step(&mut self, action: u8) -> Result<StepResult> {
+		Ale::step(self, action)
+	}
+
+	fn observation(&mut self) -> Vec<u8> {
+		Ale::observation(self).to_vec()
+	}
+
+	fn observation_into(&mut self, out: &mut [u8]) {
+		out.copy_from_slice(Ale::observation(self))
+	}
+
+	/// Returns a box of bytes shaped like [`Ale::observation_shape`].
+	fn observation_space(&self) -> Space {
+		Space::bytes(self.observation_shape().to_vec())
+	}
+
+	/// Returns a discrete space of [`Ale::action_dim`] actions.
+	fn action_space(&self) -> Space {
+		Space::Discrete { n: self.action_dim().into() }
+	}
+
+	/// Returns the current action set, see [`Ale::action_set`].
+	fn action_meanings(&self) -> Option<&[Action]> {
+		Some(self.action_set())
+	}
+}
+
+fn reset_game(ale: &mut Ale, seed: Option<i32>) -> Result<ResetInfo> {
+	match seed {
+		Some(seed) => ale.reseed(seed)?,
+		None => ale.reset_game(),
+	}
+	Ok(ResetInfo {
+		lives: ale.current_lives(),
+		episode_frame_number: ale.episode_frame_number(),
+		frame_number: ale.frame_number(),
+	})
+}
+
+impl<E: Environment + ?Sized> Environment for Box<E> {
+	type Observation = E::Observation;
+
+	fn reset(&mut self, seed: Option<i32>) -> Result<(E::Observation, ResetInfo)> { (**self).reset(seed) }
+
+	fn reset_into(&mut self, seed: Option<i32>, out: &mut [u8]) -> Result<ResetInfo>
+	where
+		E::Observation: AsRef<[u8]>,
+	{
+		(**self).reset_into(seed, out)
+	}
+
+	fn step(&mut self, action: u8) -> Result<StepResult> { (**self).step(action) }
+
+	fn observation(&mut self) -> E::Observation { (**self).observation() }
+
+	fn observation_into(&mut self, out: &mut [u8])
+	where
+		E::Observation: AsRef<[u8]>,
+	{
+		(**self).observation_into(out)
+	}
+
+	fn observation_space(&self) -> Space { (**self).observation_space() }
+
+	fn action_space(&self) -> Space { (**self).action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { (**self).action_meanings() }
+}
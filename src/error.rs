@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::{Action, BundledRom, Space};
+
+/// Result type used throughout this crate.
+pub type Result<T, E = AleError> = std::result::Result<T, E>;
+
+/// Errors that can be returned by the Arcade Learning Environment wrapper.
+#[derive(Debug)]
+pub enum AleError {
+	/// The ALE failed to create an emulator instance.
+	Create(String),
+	/// A setting key could not be passed to the ALE, e.g. because it contains a nul byte.
+	InvalidSettingKey(String),
+	/// A string setting value could not be passed to the ALE, e.g. because it contains a nul byte.
+	InvalidSettingValue { key: String, value: String },
+	/// The requested mode is not one of [`Ale::available_modes`](crate::Ale::available_modes).
+	InvalidMode { mode: i32, available: Vec<i32> },
+	/// The requested difficulty is not one of [`Ale::available_difficulties`](crate::Ale::available_difficulties).
+	InvalidDifficulty { difficulty: i32, available: Vec<i32> },
+	/// The ALE failed to load a ROM.
+	RomLoad { path: PathBuf, reason: String },
+	/// A name does not match any known ROM.
+	UnknownRom(String),
+	/// The data of a ROM was not compiled into this binary, because its cargo feature was not enabled.
+	RomNotBundled(BundledRom),
+	/// An action index is outside of the current action set.
+	InvalidAction { index: u8, action_dim: u8 },
+	/// A buffer passed in is too small to hold the requested data.
+	BufferTooSmall { expected: usize, actual: usize },
+	/// The ALE returned a size that cannot be represented, e.g. a negative one.
+	InvalidSize(i64),
+	/// A serialized state could not be decoded.
+	StateDecode(String),
+	/// A state was restored into an emulator that does not have the ROM loaded that the state was cloned from. Both
+	/// ROMs are given by their MD5 checksum, or `None` if no ROM was loaded.
+	StateRomMismatch { state: Option<String>, loaded: Option<String> },
+	/// A state file was loaded into an emulator with a different mode or difficulty set. `None` means the default was
+	/// used.
+	StateSettingMismatch { setting: &'static str, state: Option<i32>, loaded: Option<i32> },
+	/// The operation needs a ROM to be loaded.
+	NoRomLoaded,
+	/// A wrapper does not support the observation or action space of the environment it wraps.
+	UnsupportedSpace(Space),
+	/// A wrapper needs action `1` to be `FIRE`, but it is not, or the environment it wraps has too few actions.
+	/// `action` is the meaning of action `1`, if known.
+	NoFireAction { action_dim: usize, action: Option<Action> },
+	/// An IO error.
+	Io(io::Error),
+}
+impl fmt::Display for AleError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use AleError::*;
+		match self {
+			Create(reason) => write!(f, "failed to create an ALE instance: {}", reason),
+			InvalidSettingKey(key) => write!(f, "invalid setting key: {:?}", key),
+			InvalidSettingValue { key, value } => write!(f, "invalid value for setting {:?}: {:?}", key, value),
+			InvalidMode { mode, available } => write!(f, "invalid mode {}, available modes are {:?}", mode, available),
+			InvalidDifficulty { difficulty, available } => {
+				write!(f, "invalid difficulty {}, available difficulties are {:?}", difficulty, available)
+			}
+			RomLoad { path, reason } => write!(f, "failed to load ROM {}: {}", path.display(), reason),
+			UnknownRom(name) => write!(f, "unknown ROM: {:?}", name),
+			RomNotBundled(rom) => write!(
+				f,
+				"ROM {} is not bundled, enable the `{}` feature of the `ale` crate to bundle it",
+				rom,
+				rom.feature()
+			),
+			InvalidAction { index, action_dim } => {
+				write!(f, "invalid action index {}, action set has {} actions", index, action_dim)
+			}
+			BufferTooSmall { expected, actual } => {
+				write!(f, "buffer too small: expected at least {} bytes, got {}", expected, actual)
+			}
+			InvalidSize(size) => write!(f, "invalid size returned by the ALE: {}", size),
+			StateDecode(reason) => write!(f, "failed to decode state: {}", reason),
+			StateRomMismatch { state, loaded } => write!(
+				f,
+				"state was cloned with ROM {} but ROM {} is loaded",
+				state.as_deref().unwrap_or("<none>"),
+				loaded.as_deref().unwrap_or("<none>")
+			),
+			StateSettingMismatch { setting, state, loaded } => {
+				let show = |value: &Option<i32>| value.map_or_else(|| "default".to_owned(), |v| v.to_string());
+				write!(f, "state was saved with {} {} but {} {} is set", setting, show(state), setting, show(loaded))
+			}
+			NoRomLoaded => write!(f, "no ROM is loaded"),
+			UnsupportedSpace(space) => write!(f, "unsupported space: {}", space),
+			NoFireAction { action: Some(action), .. } if *action != Action::Fire => {
+				write!(f, "action 1 of the environment is {:?} rather than FIRE", action)
+			}
+			NoFireAction { action_dim, .. } => {
+				write!(f, "environment has {} actions, but pressing FIRE on reset takes actions 1 and 2", action_dim)
+			}
+			Io(e) => write!(f, "IO error: {}", e),
+		}
+	}
+}
+impl Error for AleError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			AleError::Io(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+impl From<io::Error> for AleError {
+	fn from(e: io::Error) -> AleError { AleError::Io(e) }
+}
+
+/// Checks that `buf` can hold at least `expected` bytes.
+pub(crate) fn check_buffer(buf: &[u8], expected: usize) -> Result<()> {
+	if buf.len() < expected {
+		Err(AleError::BufferTooSmall { expected, actual: buf.len() })
+	} else {
+		Ok(())
+	}
+}
@@ -1,5 +1,36 @@
+mod action;
 mod atari;
-mod parallel;
+mod config;
+mod env;
+mod error;
+mod observation;
+pub mod palette;
+mod registry;
+mod rom;
+mod rom_cache;
+mod settings;
+mod snapshot;
+mod space;
+mod state;
+mod state_file;
+mod step;
+mod vec_env;
+pub mod wrappers;
 
-pub use atari::{Ale, BundledRom, LoggerMode};
-pub use parallel::ParallelAtari;
\ No newline at end of file
+pub use action::{Action, ActionSet};
+pub use atari::{Ale, LoggerMode};
+pub use config::AleConfig;
+pub use env::{Environment, ResetInfo};
+pub use error::{AleError, Result};
+pub use observation::{Layout, ObservationType};
+pub use palette::Palette;
+pub use registry::{DuplicateRom, RomRegistry, UnreadableRom, ROMS_DIR_ENV};
+pub use rom::{BundledRom, RomInfo};
+pub use rom_cache::ROM_CACHE_DIR_ENV;
+pub use settings::{Setting, SettingKey, SettingType};
+pub use snapshot::EnvSnapshot;
+pub use space::{Dtype, Space};
+pub use state::AleState;
+pub use state_file::{StateFileHeader, StateFileOptions};
+pub use step::StepResult;
+pub use vec_env::{AutoReset, EnvInfo, VecEnv, VecStep};
\ No newline at end of file
@@ -0,0 +1,111 @@
+/// Kind of observation returned by [`Ale::observation`](crate::Ale::observation).
+///
+/// Every observation is a flat buffer of `u8`s, laid out row-major according to
+/// [`Ale::observation_shape`](crate::Ale::observation_shape).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ObservationType {
+	/// RGB screen, with shape `[height, width, 3]`.
+	#[default]
+	Rgb,
+	/// Grayscale screen, with shape `[height, width]`, where `0 = black` and `255 = white`.
+	Grayscale,
+	/// Contents of the Atari's RAM, with shape `[ram_size]`.
+	Ram,
+	/// Raw palette indices of the screen, with shape `[height, width]`.
+	///
+	/// This is a third of the size of an RGB screen, and can be converted to one using the game's palette.
+	Palette,
+}
+
+/// Memory layout of image observations that are stacked or batched together, by
+/// [`FrameStack`](crate::wrappers::FrameStack) and [`VecEnv`](crate::VecEnv).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Layout {
+	/// Channels are the last dimension, like the images themselves, e.g. `[height, width, channels]`.
+	#[default]
+	ChannelsLast,
+	/// Channels are the first dimension, e.g. `[channels, height, width]`, as most PyTorch models expect.
+	ChannelsFirst,
+}
+impl Layout {
+	/// Returns the shape of `count` images of shape `[height, width, channels]` stacked along the channel dimension.
+	pub fn stacked_shape(&self, [height, width, channels]: [usize; 3], count: usize) -> Vec<usize> {
+		match self {
+			Layout::ChannelsLast => vec![height, width, count * channels],
+			Layout::ChannelsFirst => vec![count * channels, height, width],
+		}
+	}
+
+	/// Copies an image of shape `[height, width, channels]` into `out`, as the image at `index` of `count` images
+	/// stacked along the channel dimension.
+	pub(crate) fn write_stacked(&self, image: &[u8], shape: [usize; 3], index: usize, count: usize, out: &mut [u8]) {
+		let [height, width, channels] = shape;
+		match self {
+			Layout::ChannelsLast => {
+				let stride = count * channels;
+				for (pixel, out) in image.chunks_exact(channels).zip(out.chunks_exact_mut(stride)) {
+					out[index * channels..][..channels].copy_from_slice(pixel);
+				}
+			}
+			Layout::ChannelsFirst => {
+				let plane = height * width;
+				for channel in 0..channels {
+					let out = &mut out[(index * channels + channel) * plane..][..plane];
+					for (out, pixel) in out.iter_mut().zip(image[channel..].iter().step_by(channels)) {
+						*out = *pixel;
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Stacks images one element at a time, straight from the definition of each layout.
+	fn naive_stack(layout: Layout, images: &[Vec<u8>], [height, width, channels]: [usize; 3]) -> Vec<u8> {
+		let count = images.len();
+		let mut out = vec![0; height * width * channels * count];
+		for (index, image) in images.iter().enumerate() {
+			for y in 0..height {
+				for x in 0..width {
+					for channel in 0..channels {
+						let out_index = match layout {
+							Layout::ChannelsLast => ((y * width + x) * count + index) * channels + channel,
+							Layout::ChannelsFirst => ((index * channels + channel) * height + y) * width + x,
+						};
+						out[out_index] = image[(y * width + x) * channels + channel];
+					}
+				}
+			}
+		}
+		out
+	}
+
+	#[test]
+	fn write_stacked_matches_naive_stacking() {
+		for &layout in &[Layout::ChannelsLast, Layout::ChannelsFirst] {
+			for &shape in &[[3, 4, 1], [3, 4, 3], [1, 1, 2]] {
+				for &count in &[1, 2, 4] {
+					let size: usize = shape.iter().product();
+					let images: Vec<Vec<u8>> =
+						(0..count).map(|index| (0..size).map(|i| (index * size + i) as u8).collect()).collect();
+					let mut out = vec![0; size * count];
+					for (index, image) in images.iter().enumerate() {
+						layout.write_stacked(image, shape, index, count, &mut out);
+					}
+					assert_eq!(out, naive_stack(layout, &images, shape), "{:?} {:?} x{}", layout, shape, count);
+					assert_eq!(layout.stacked_shape(shape, count).iter().product::<usize>(), out.len());
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn stacked_shape() {
+		assert_eq!(Layout::ChannelsLast.stacked_shape([210, 160, 3], 4), [210, 160, 12]);
+		assert_eq!(Layout::ChannelsFirst.stacked_shape([84, 84, 1], 4), [4, 84, 84]);
+	}
+}
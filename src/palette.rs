@@ -0,0 +1,220 @@
+//! Conversion of raw palette-index screens (see [`ObservationType::Palette`](crate::ObservationType::Palette)) to
+//! RGB or grayscale.
+//!
+//! The palettes are the ones used by the ALE's emulator, so converting an NTSC screen gives the same result as
+//! [`Ale::get_screen_rgb`](crate::Ale::get_screen_rgb) and [`Ale::get_screen_grayscale`](crate::Ale::get_screen_grayscale).
+
+use crate::error::check_buffer;
+use crate::Result;
+
+/// Returns the grayscale value of an RGB colour, using the same luminance weights as the ALE.
+///
+/// Like the ALE, this computes in single precision and rounds half away from zero.
+pub fn luminance([r, g, b]: [u8; 3]) -> u8 {
+	(r as f32 * 0.2989 + g as f32 * 0.5870 + b as f32 * 0.1140).round() as u8
+}
+
+/// Colours of the even palette indices of the NTSC palette. Odd indices share the colour of the index below them.
+#[rustfmt::skip]
+const NTSC: [u32; 128] = [
+	0x000000, 0x4a4a4a, 0x6f6f6f, 0x8e8e8e, 0xaaaaaa, 0xc0c0c0, 0xd6d6d6, 0xececec,
+	0x484800, 0x69690f, 0x86861d, 0xa2a22a, 0xbbbb35, 0xd2d240, 0xe8e84a, 0xfcfc54,
+	0x7c2c00, 0x904811, 0xa26221, 0xb47a30, 0xc3903d, 0xd2a44a, 0xdfb755, 0xecc860,
+	0x901c00, 0xa33915, 0xb55328, 0xc66c3a, 0xd5824a, 0xe39759, 0xf0aa67, 0xfcbc74,
+	0x940000, 0xa71a1a, 0xb83232, 0xc84848, 0xd65c5c, 0xe46f6f, 0xf08080, 0xfc9090,
+	0x840064, 0x97197a, 0xa8308f, 0xb846a2, 0xc659b3, 0xd46cc3, 0xe07cd2, 0xec8ce0,
+	0x500084, 0x68199a, 0x7d30ad, 0x9246c0, 0xa459d0, 0xb56ce0, 0xc57cee, 0xd48cfc,
+	0x140090, 0x331aa3, 0x4e32b5, 0x6848c6, 0x7f5cd5, 0x956fe3, 0xa980f0, 0xbc90fc,
+	0x000094, 0x181aa7, 0x2d32b8, 0x4248c8, 0x545cd6, 0x656fe4, 0x7580f0, 0x8490fc,
+	0x001c88, 0x183b9d, 0x2d57b0, 0x4272c2, 0x548ad2, 0x65a0e1, 0x75b5ef, 0x84c8fc,
+	0x003064, 0x185080, 0x2d6d98, 0x4288b0, 0x54a0c5, 0x65b7d9, 0x75cceb, 0x84e0fc,
+	0x004030, 0x18624e, 0x2d8169, 0x429e82, 0x54b899, 0x65d1ae, 0x75e7c2, 0x84fcd4,
+	0x004400, 0x1a661a, 0x328432, 0x48a048, 0x5cba5c, 0x6fd26f, 0x80e880, 0x90fc90,
+	0x143c00, 0x355f18, 0x527e2d, 0x6e9c42, 0x87b754, 0x9ed065, 0xb4e775, 0xc8fc84,
+	0x303800, 0x505916, 0x6d762b, 0x88923e, 0xa0ab4f, 0xb7c25f, 0xccd86e, 0xe0ec7c,
+	0x482c00, 0x694d14, 0x866a26, 0xa28638, 0xbb9f47, 0xd2b656, 0xe8cc63, 0xfce070,
+];
+
+/// Colours of the even palette indices of the PAL palette. Odd indices share the colour of the index below them.
+#[rustfmt::skip]
+const PAL: [u32; 128] = [
+	0x000000, 0x2b2b2b, 0x525252, 0x767676, 0x979797, 0xb6b6b6, 0xd2d2d2, 0xececec,
+	0x000000, 0x2b2b2b, 0x525252, 0x767676, 0x979797, 0xb6b6b6, 0xd2d2d2, 0xececec,
+	0x805800, 0x96711a, 0xab8732, 0xbe9c48, 0xcfaf5c, 0xdfc06f, 0xeed180, 0xfce090,
+	0x445c00, 0x5e791a, 0x769332, 0x8cac48, 0xa0c25c, 0xb3d76f, 0xc4ea80, 0xd4fc90,
+	0x703400, 0x89511a, 0xa06b32, 0xb68448, 0xc99a5c, 0xdcaf6f, 0xecc280, 0xfcd490,
+	0x006414, 0x1a8035, 0x329852, 0x48b06e, 0x5cc587, 0x6fd99e, 0x80ebb4, 0x90fcc8,
+	0x700014, 0x891a35, 0xa03252, 0xb6486e, 0xc95c87, 0xdc6f9e, 0xec80b4, 0xfc90c8,
+	0x005c5c, 0x1a7676, 0x328e8e, 0x48a4a4, 0x5cb8b8, 0x6fcbcb, 0x80dcdc, 0x90ecec,
+	0x70005c, 0x841a74, 0x963289, 0xa8489e, 0xb75cb0, 0xc66fc1, 0xd380d1, 0xe090e0,
+	0x003c70, 0x195a89, 0x2f75a0, 0x448eb6, 0x57a5c9, 0x68badc, 0x79ceec, 0x88e0fc,
+	0x580070, 0x6e1a89, 0x8332a0, 0x9648b6, 0xa75cc9, 0xb76fdc, 0xc680ec, 0xd490fc,
+	0x002070, 0x193f89, 0x2f5aa0, 0x4474b6, 0x578bc9, 0x68a1dc, 0x79b5ec, 0x88c8fc,
+	0x340080, 0x4a1a96, 0x5f32ab, 0x7248be, 0x835ccf, 0x936fdf, 0xa280ee, 0xb090fc,
+	0x000088, 0x1a1a9d, 0x3232b0, 0x4848c2, 0x5c5cd2, 0x6f6fe1, 0x8080ef, 0x9090fc,
+	0x000000, 0x2b2b2b, 0x525252, 0x767676, 0x979797, 0xb6b6b6, 0xd2d2d2, 0xececec,
+	0x000000, 0x2b2b2b, 0x525252, 0x767676, 0x979797, 0xb6b6b6, 0xd2d2d2, 0xececec,
+];
+
+/// Colours of the even palette indices of the SECAM palette. SECAM only has 8 colours, repeated for every hue.
+#[rustfmt::skip]
+const SECAM: [u32; 128] = [
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+	0x000000, 0x2121ff, 0xf03c79, 0xff50ff, 0x7fff00, 0x7fffff, 0xffff3f, 0xffffff,
+];
+
+/// Television standard whose palette is used to colour a screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Palette {
+	/// The palette used by the ALE for all of its screens.
+	#[default]
+	Ntsc,
+	Pal,
+	Secam,
+}
+impl Palette {
+	fn table(&self) -> &'static [u32; 128] {
+		match self {
+			Palette::Ntsc => &NTSC,
+			Palette::Pal => &PAL,
+			Palette::Secam => &SECAM,
+		}
+	}
+
+	/// Returns the RGB colour of a palette index.
+	pub fn rgb(&self, index: u8) -> [u8; 3] {
+		let c = self.table()[(index >> 1) as usize];
+		[(c >> 16) as u8, (c >> 8) as u8, c as u8]
+	}
+
+	/// Returns the grayscale value of a palette index, where `0 = black` and `255 = white`.
+	///
+	/// This uses the same luminance weights as the ALE.
+	pub fn grayscale(&self, index: u8) -> u8 {
+		luminance(self.rgb(index))
+	}
+
+	/// Converts a screen of palette indices to RGB, writing 3 bytes per pixel into `out`.
+	///
+	/// Returns [`AleError::BufferTooSmall`](crate::AleError::BufferTooSmall) if `out` is smaller than
+	/// `screen.len() * 3`.
+	pub fn to_rgb(&self, screen: &[u8], out: &mut [u8]) -> Result<()> {
+		check_buffer(out, screen.len() * 3)?;
+		for (&index, pixel) in screen.iter().zip(out.chunks_exact_mut(3)) {
+			pixel.copy_from_slice(&self.rgb(index));
+		}
+		Ok(())
+	}
+
+	/// Converts a screen of palette indices to grayscale, writing 1 byte per pixel into `out`.
+	///
+	/// Returns [`AleError::BufferTooSmall`](crate::AleError::BufferTooSmall) if `out` is smaller than `screen`.
+	pub fn to_grayscale(&self, screen: &[u8], out: &mut [u8]) -> Result<()> {
+		check_buffer(out, screen.len())?;
+		let mut table = [0u8; 128];
+		for (i, value) in table.iter_mut().enumerate() {
+			*value = self.grayscale((i << 1) as u8);
+		}
+		for (&index, value) in screen.iter().zip(out.iter_mut()) {
+			*value = table[(index >> 1) as usize];
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Grayscale values of the even NTSC palette indices, computed independently of this module with the single
+	/// precision formula the ALE's `ColourPalette` uses for `getScreenGrayscale`.
+	#[rustfmt::skip]
+	const NTSC_GRAYSCALE: [u8; 128] = [
+		0, 74, 111, 142, 170, 192, 214, 236, 64, 95, 122, 148, 172, 193, 214, 233,
+		63, 87, 110, 131, 150, 167, 184, 199, 59, 85, 107, 129, 148, 167, 183, 199,
+		44, 68, 90, 110, 128, 146, 161, 176, 51, 74, 95, 115, 132, 149, 164, 178,
+		39, 63, 85, 107, 125, 143, 159, 174, 22, 49, 73, 96, 116, 136, 153, 169,
+		17, 41, 64, 85, 104, 121, 137, 153, 32, 60, 85, 109, 130, 150, 168, 186,
+		40, 69, 95, 120, 141, 162, 182, 200, 43, 74, 101, 127, 151, 173, 193, 212,
+		40, 71, 98, 124, 147, 169, 189, 207, 41, 74, 104, 132, 157, 181, 203, 223,
+		47, 79, 107, 133, 157, 179, 200, 220, 47, 79, 107, 133, 157, 179, 200, 220,
+	];
+
+	#[test]
+	fn ntsc_grayscale_matches_ale() {
+		for index in 0..=255u8 {
+			assert_eq!(Palette::Ntsc.grayscale(index), NTSC_GRAYSCALE[index as usize >> 1], "index {:#04x}", index);
+		}
+	}
+
+	#[test]
+	fn ntsc_screen_conversion_matches_per_index() {
+		let screen: Vec<u8> = (0..=255).collect();
+		let mut rgb = vec![0; 256 * 3];
+		let mut grayscale = vec![0; 256];
+		Palette::Ntsc.to_rgb(&screen, &mut rgb).unwrap();
+		Palette::Ntsc.to_grayscale(&screen, &mut grayscale).unwrap();
+		for &index in &screen {
+			let i = index as usize;
+			assert_eq!(rgb[i * 3..i * 3 + 3], Palette::Ntsc.rgb(index));
+			assert_eq!(Palette::Ntsc.rgb(index), Palette::Ntsc.rgb(index & !1));
+			assert_eq!(grayscale[i], NTSC_GRAYSCALE[i >> 1]);
+		}
+	}
+
+	#[test]
+	fn ntsc_rgb_spot_checks() {
+		assert_eq!(Palette::Ntsc.rgb(0x00), [0x00, 0x00, 0x00]);
+		assert_eq!(Palette::Ntsc.rgb(0x0e), [0xec, 0xec, 0xec]);
+		assert_eq!(Palette::Ntsc.rgb(0x1f), [0xfc, 0xfc, 0x54]);
+		assert_eq!(Palette::Ntsc.rgb(0x42), [0xa7, 0x1a, 0x1a]);
+		assert_eq!(Palette::Ntsc.rgb(0xfe), [0xfc, 0xe0, 0x70]);
+	}
+
+	#[test]
+	fn pal_spot_checks() {
+		assert_eq!(Palette::Pal.rgb(0x00), [0, 0, 0]);
+		assert_eq!(Palette::Pal.rgb(0x2b), [223, 192, 111]);
+		assert_eq!(Palette::Pal.grayscale(0x2b), 192);
+		assert_eq!(Palette::Pal.rgb(0x45), [160, 107, 50]);
+		assert_eq!(Palette::Pal.grayscale(0x45), 116);
+		assert_eq!(Palette::Pal.rgb(0x9c), [121, 206, 236]);
+		assert_eq!(Palette::Pal.grayscale(0x9c), 184);
+		assert_eq!(Palette::Pal.rgb(0xfe), [236, 236, 236]);
+	}
+
+	#[test]
+	fn secam_spot_checks() {
+		assert_eq!(Palette::Secam.rgb(0x02), [33, 33, 255]);
+		assert_eq!(Palette::Secam.grayscale(0x02), 58);
+		assert_eq!(Palette::Secam.rgb(0x05), [240, 60, 121]);
+		assert_eq!(Palette::Secam.grayscale(0x05), 121);
+		assert_eq!(Palette::Secam.rgb(0x38), [127, 255, 0]);
+		assert_eq!(Palette::Secam.grayscale(0x38), 188);
+		assert_eq!(Palette::Secam.rgb(0xff), [255, 255, 255]);
+		// SECAM repeats the same 8 colours for every hue
+		for index in 0..=255u8 {
+			assert_eq!(Palette::Secam.rgb(index), Palette::Secam.rgb(index & 0x0f));
+		}
+	}
+
+	#[test]
+	fn buffers_too_small() {
+		assert!(Palette::Ntsc.to_rgb(&[0; 4], &mut [0; 11]).is_err());
+		assert!(Palette::Ntsc.to_grayscale(&[0; 4], &mut [0; 3]).is_err());
+	}
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::{Ale, AleError, BundledRom, Result};
+
+/// Environment variable read by [`RomRegistry::from_env`].
+pub const ROMS_DIR_ENV: &str = "ALE_ROMS_DIR";
+
+/// A ROM file that identifies as the same game as a ROM that was already found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateRom {
+	/// The game both files identify as.
+	pub rom: BundledRom,
+	/// The file that is used for the game.
+	pub kept: PathBuf,
+	/// The file that was ignored.
+	pub ignored: PathBuf,
+}
+
+/// A file or directory entry that could not be read while scanning a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnreadableRom {
+	/// The file that could not be read, or the scanned directory if the entry itself could not be read.
+	pub path: PathBuf,
+	/// Why it could not be read.
+	pub reason: String,
+}
+
+/// ROMs found in a directory, identified by their MD5 checksum rather than their file name.
+///
+/// This allows ROMs to be loaded from a shared directory, even when they have non-canonical file names, and falls back
+/// to the bundled ROMs for games that were not found.
+///
+/// # Examples
+/// ```no_run
+/// # use ale::{Ale, RomRegistry};
+/// let registry = RomRegistry::scan_dir("/shared/roms").unwrap();
+/// for path in registry.unknown() {
+///     eprintln!("unknown ROM: {}", path.display());
+/// }
+/// for file in registry.unreadable() {
+///     eprintln!("could not read {}: {}", file.path.display(), file.reason);
+/// }
+/// let mut ale = Ale::new(108_000);
+/// registry.load(&mut ale, "ms_pacman").unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RomRegistry {
+	roms: HashMap<BundledRom, PathBuf>,
+	unknown: Vec<PathBuf>,
+	duplicates: Vec<DuplicateRom>,
+	unreadable: Vec<UnreadableRom>,
+}
+impl RomRegistry {
+	/// Creates an empty registry, which only loads bundled ROMs.
+	pub fn new() -> RomRegistry {
+		RomRegistry::default()
+	}
+
+	/// Scans the directory named by the `ALE_ROMS_DIR` environment variable, or returns an empty registry if it is
+	/// not set.
+	pub fn from_env() -> Result<RomRegistry> {
+		match std::env::var_os(ROMS_DIR_ENV) {
+			Some(dir) => RomRegistry::scan_dir(dir),
+			None => Ok(RomRegistry::new()),
+		}
+	}
+
+	/// Scans a directory for `.bin` files, and identifies each of them by its MD5 checksum.
+	///
+	/// Files that do not match any known game are reported by [`RomRegistry::unknown`], and files that match a game
+	/// that was already found are reported by [`RomRegistry::duplicates`]. Files are scanned in path order, so the
+	/// first file of a game in that order is the one that is used. Files that cannot be read are skipped and reported
+	/// by [`RomRegistry::unreadable`], so only failing to read the directory itself is an error.
+	pub fn scan_dir(dir: impl AsRef<Path>) -> Result<RomRegistry> {
+		let dir = dir.as_ref();
+		let mut registry = RomRegistry::new();
+		let mut paths = vec![];
+		for entry in std::fs::read_dir(dir)? {
+			let path = match entry {
+				Ok(entry) => entry.path(),
+				Err(e) => {
+					registry.unreadable.push(UnreadableRom { path: dir.to_owned(), reason: e.to_string() });
+					continue;
+				}
+			};
+			let is_bin = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("bin"));
+			if is_bin && path.is_file() {
+				paths.push(path);
+			}
+		}
+		paths.sort();
+
+		for path in paths {
+			let data = match std::fs::read(&path) {
+				Ok(data) => data,
+				Err(e) => {
+					registry.unreadable.push(UnreadableRom { path, reason: e.to_string() });
+					continue;
+				}
+			};
+			match BundledRom::from_md5(&format!("{:x}", md5::compute(&data))) {
+				Some(rom) => match registry.roms.get(&rom) {
+					Some(kept) => registry.duplicates.push(DuplicateRom { rom, kept: kept.clone(), ignored: path }),
+					None => {
+						registry.roms.insert(rom, path);
+					}
+				},
+				None => registry.unknown.push(path),
+			}
+		}
+		Ok(registry)
+	}
+
+	/// Returns the file found for a game, if any.
+	pub fn path(&self, rom: BundledRom) -> Option<&Path> {
+		self.roms.get(&rom).map(PathBuf::as_path)
+	}
+
+	/// Returns every game that was found, along with its file, in alphabetical order of the games.
+	pub fn roms(&self) -> Vec<(BundledRom, &Path)> {
+		let mut roms: Vec<_> = self.roms.iter().map(|(&rom, path)| (rom, path.as_path())).collect();
+		roms.sort_by_key(|&(rom, _)| rom.id());
+		roms
+	}
+
+	/// Returns the `.bin` files that did not match any known game.
+	pub fn unknown(&self) -> &[PathBuf] {
+		&self.unknown
+	}
+
+	/// Returns the files that were ignored because another file was already found for the same game.
+	pub fn duplicates(&self) -> &[DuplicateRom] {
+		&self.duplicates
+	}
+
+	/// Returns the files that were skipped because they could not be read.
+	pub fn unreadable(&self) -> &[UnreadableRom] {
+		&self.unreadable
+	}
+
+	/// Loads a game into `ale`, given its identifier (anything accepted by [`BundledRom::from_str`]).
+	///
+	/// The file found in the scanned directory is preferred, and the bundled ROM is used otherwise. Either way the ROM
+	/// is given its canonical file name, so that the ALE picks the correct settings for it.
+	///
+	/// [`BundledRom::from_str`]: std::str::FromStr::from_str
+	pub fn load(&self, ale: &mut Ale, id: &str) -> Result<()> {
+		let rom: BundledRom = id.parse()?;
+		match self.roms.get(&rom) {
+			Some(path) => {
+				let data = std::fs::read(path)
+					.map_err(|e| AleError::RomLoad { path: path.clone(), reason: e.to_string() })?;
+				ale.load_rom_bytes(rom.filename(), &data)
+			}
+			None => ale.load_rom(rom),
+		}
+	}
+}
+
+#[cfg(all(test, unix, feature = "rom-breakout", not(feature = "no-bundled-roms")))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn scan_dir_skips_unreadable_files() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let dir = std::env::temp_dir().join(format!("ale-rs-registry-test-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let breakout = BundledRom::Breakout.data().unwrap();
+		std::fs::write(dir.join("a.bin"), &breakout).unwrap();
+		std::fs::write(dir.join("b.BIN"), &breakout).unwrap();
+		std::fs::write(dir.join("c.bin"), b"not a rom").unwrap();
+		std::fs::write(dir.join("d.txt"), &breakout).unwrap();
+		let locked = dir.join("e.bin");
+		std::fs::write(&locked, &breakout).unwrap();
+		std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+		let registry = RomRegistry::scan_dir(&dir).unwrap();
+		assert_eq!(registry.path(BundledRom::Breakout), Some(dir.join("a.bin").as_path()));
+		assert_eq!(registry.unknown(), [dir.join("c.bin")]);
+		// Permissions are not enforced for root, in which case the locked file is just another duplicate
+		if std::fs::read(&locked).is_err() {
+			assert_eq!(registry.duplicates().len(), 1);
+			assert_eq!(registry.unreadable().len(), 1);
+			assert_eq!(registry.unreadable()[0].path, locked);
+		} else {
+			assert_eq!(registry.duplicates().len(), 2);
+			assert!(registry.unreadable().is_empty());
+		}
+		assert_eq!(registry.duplicates()[0].ignored, dir.join("b.BIN"));
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
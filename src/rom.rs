@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Ale, AleError, Result};
+
+// `BundledRom` and its per-game tables are generated from `roms/manifest.toml` by `cargo xtask gen-roms`.
+mod generated;
+#[cfg(feature = "compressed-roms")]
+mod compressed;
+
+pub use generated::BundledRom;
+
+impl BundledRom {
+	/// Returns the identifier of the game used by the ALE, i.e. the filename without its extension, e.g. `"ms_pacman"`.
+	pub fn id(&self) -> &'static str {
+		let filename = self.filename();
+		&filename[..filename.len() - ".bin".len()]
+	}
+
+	/// Returns the game whose ROM has the given MD5 checksum, as a lowercase hex string.
+	pub fn from_md5(md5: &str) -> Option<BundledRom> {
+		BundledRom::ALL.iter().copied().find(|rom| rom.md5() == md5)
+	}
+
+	/// Returns whether the game is part of the Atari-57 benchmark set.
+	pub fn is_atari57(&self) -> bool {
+		BundledRom::ATARI_57.contains(self)
+	}
+
+	/// Returns information about the game, by loading it into a new emulator instance.
+	pub fn info(&self) -> Result<RomInfo> {
+		let mut ale = Ale::try_new(0)?;
+		ale.load_rom(*self)?;
+		Ok(RomInfo {
+			rom: *self,
+			modes: ale.available_modes()?,
+			difficulties: ale.available_difficulties()?,
+			minimal_action_count: ale.minimal_action_set()?.len(),
+			atari57: self.is_atari57(),
+		})
+	}
+
+	/// Returns whether the ROM's data was compiled into this binary. See [`BundledRom::data`].
+	pub fn is_bundled(&self) -> bool {
+		#[cfg(not(feature = "compressed-roms"))]
+		let bundled = self.included_data().is_some();
+		#[cfg(feature = "compressed-roms")]
+		let bundled = compressed::contains(self.filename());
+		bundled
+	}
+
+	/// Returns the raw binary data of the ROM.
+	///
+	/// Which ROMs are compiled in is controlled by cargo features: each ROM has its own feature (see
+	/// [`BundledRom::feature`]), and the `atari5`, `atari57` and `all-roms` features enable groups of them. `all-roms`
+	/// is enabled by default, and `no-bundled-roms` disables every ROM regardless of the other features.
+	///
+	/// With the `compressed-roms` feature the ROMs are stored deflate compressed, and are decompressed on every call,
+	/// so the data is owned. Otherwise it is borrowed from the binary.
+	///
+	/// Returns [`AleError::RomNotBundled`] if the ROM was not compiled in.
+	pub fn data(&self) -> Result<Cow<'static, [u8]>> {
+		#[cfg(not(feature = "compressed-roms"))]
+		let data = self.included_data().map(Cow::Borrowed);
+		#[cfg(feature = "compressed-roms")]
+		let data = compressed::data(self.filename())?.map(Cow::Owned);
+		data.ok_or(AleError::RomNotBundled(*self))
+	}
+}
+impl fmt::Display for BundledRom {
+	/// Displays the identifier of the game, e.g. `ms_pacman`, which can be parsed back with [`str::parse`].
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.id()) }
+}
+impl FromStr for BundledRom {
+	type Err = AleError;
+
+	/// Parses a game from its identifier, filename or variant name, ignoring case, underscores, dashes and spaces.
+	///
+	/// For example `"ms_pacman"`, `"ms_pacman.bin"`, `"MsPacman"` and `"ms-pacman"` all parse to
+	/// [`BundledRom::MsPacman`].
+	fn from_str(s: &str) -> Result<BundledRom> {
+		fn normalize(s: &str) -> String {
+			let s = s.strip_suffix(".bin").unwrap_or(s);
+			s.chars().filter(|c| !matches!(c, '_' | '-' | ' ')).flat_map(char::to_lowercase).collect()
+		}
+		let name = normalize(s);
+		BundledRom::ALL
+			.iter()
+			.copied()
+			.find(|rom| normalize(rom.id()) == name)
+			.ok_or_else(|| AleError::UnknownRom(s.to_owned()))
+	}
+}
+
+/// Information about a game, returned by [`BundledRom::info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RomInfo {
+	/// The game this information is about.
+	pub rom: BundledRom,
+	/// The modes available for the game, see [`Ale::available_modes`].
+	pub modes: Vec<i32>,
+	/// The difficulties available for the game, see [`Ale::available_difficulties`].
+	pub difficulties: Vec<i32>,
+	/// Number of actions in the game's minimal action set.
+	pub minimal_action_count: usize,
+	/// Whether the game is part of the Atari-57 benchmark set.
+	pub atari57: bool,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn names_round_trip() {
+		for &rom in BundledRom::ALL.iter() {
+			assert_eq!(rom.id().parse::<BundledRom>().unwrap(), rom);
+			assert_eq!(rom.to_string().parse::<BundledRom>().unwrap(), rom);
+			assert_eq!(rom.filename().parse::<BundledRom>().unwrap(), rom);
+			assert_eq!(format!("{:?}", rom).parse::<BundledRom>().unwrap(), rom);
+			assert_eq!(rom.filename(), format!("{}.bin", rom.id()));
+			assert_eq!(BundledRom::from_md5(rom.md5()), Some(rom));
+		}
+		assert_eq!("MS-PACMAN".parse::<BundledRom>().unwrap(), BundledRom::MsPacman);
+		assert!(matches!("not_a_game".parse::<BundledRom>(), Err(AleError::UnknownRom(name)) if name == "not_a_game"));
+	}
+
+	#[test]
+	fn benchmark_sets() {
+		assert!(BundledRom::ATARI_57.iter().all(|rom| rom.is_atari57() && BundledRom::ALL.contains(rom)));
+		assert!(BundledRom::ATARI_5.iter().all(BundledRom::is_atari57));
+		assert_eq!(BundledRom::ALL.iter().filter(|rom| rom.is_atari57()).count(), 57);
+	}
+
+	#[test]
+	fn data_matches_features() {
+		for &rom in BundledRom::ALL.iter() {
+			match rom.data() {
+				Ok(data) => {
+					assert!(rom.is_bundled(), "{} has data but is not bundled", rom);
+					assert_eq!(format!("{:x}", md5::compute(&data)), rom.md5());
+				}
+				Err(AleError::RomNotBundled(missing)) => {
+					assert!(!rom.is_bundled(), "{} is bundled but has no data", rom);
+					assert_eq!(missing, rom);
+				}
+				Err(e) => panic!("failed to read {}: {}", rom, e),
+			}
+			if cfg!(feature = "no-bundled-roms") {
+				assert!(!rom.is_bundled());
+			} else if cfg!(feature = "all-roms") {
+				assert!(rom.is_bundled());
+			}
+		}
+	}
+}
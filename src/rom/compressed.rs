@@ -0,0 +1,31 @@
+//! ROMs stored as a single deflate compressed blob, written by the build script when the `compressed-roms` feature is
+//! enabled.
+
+use std::io::Read;
+
+use flate2::read::DeflateDecoder;
+
+use crate::Result;
+
+/// Every bundled ROM, each compressed separately and concatenated.
+static BLOB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/roms.deflate"));
+
+// Defines `INDEX: &[(&str, usize, usize, usize)]`, holding the filename, start and end in `BLOB`, and uncompressed size
+// of each bundled ROM.
+include!(concat!(env!("OUT_DIR"), "/roms_index.rs"));
+
+/// Returns whether the ROM with the given filename was bundled.
+pub(super) fn contains(filename: &str) -> bool {
+	INDEX.iter().any(|&(name, ..)| name == filename)
+}
+
+/// Decompresses the ROM with the given filename, or returns `None` if it was not bundled.
+pub(super) fn data(filename: &str) -> Result<Option<Vec<u8>>> {
+	let (start, end, size) = match INDEX.iter().find(|&&(name, ..)| name == filename) {
+		Some(&(_, start, end, size)) => (start, end, size),
+		None => return Ok(None),
+	};
+	let mut data = Vec::with_capacity(size);
+	DeflateDecoder::new(&BLOB[start..end]).read_to_end(&mut data)?;
+	Ok(Some(data))
+}
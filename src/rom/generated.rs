@@ -0,0 +1,991 @@
+// Generated by `cargo xtask gen-roms` from `roms/manifest.toml`, do not edit by hand.
+
+/// Enum of ROMs that come bundled with the libarary.
+///
+/// Every game is listed regardless of which ROMs were compiled in. See [`BundledRom::data`] for how to select them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BundledRom {
+	Adventure,
+	AirRaid,
+	Alien,
+	Amidar,
+	Assault,
+	Asterix,
+	Asteroids,
+	Atlantis,
+	Atlantis2,
+	Backgammon,
+	BankHeist,
+	BasicMath,
+	BattleZone,
+	BeamRider,
+	Berzerk,
+	Blackjack,
+	Bowling,
+	Boxing,
+	Breakout,
+	Carnival,
+	Casino,
+	Centipede,
+	ChopperCommand,
+	Combat,
+	CrazyClimber,
+	Crossbow,
+	Darkchambers,
+	Defender,
+	DemonAttack,
+	DonkeyKong,
+	DoubleDunk,
+	Earthworld,
+	ElevatorAction,
+	Enduro,
+	Entombed,
+	Et,
+	FishingDerby,
+	FlagCapture,
+	Freeway,
+	Frogger,
+	Frostbite,
+	Galaxian,
+	Gopher,
+	Gravitar,
+	Hangman,
+	HauntedHouse,
+	Hero,
+	HumanCannonball,
+	IceHockey,
+	Jamesbond,
+	JourneyEscape,
+	Joust,
+	Kaboom,
+	Kangaroo,
+	KeystoneKapers,
+	KingKong,
+	Klax,
+	Koolaid,
+	Krull,
+	KungFuMaster,
+	LaserGates,
+	LostLuggage,
+	MarioBros,
+	MazeCraze,
+	MiniatureGolf,
+	MontezumaRevenge,
+	MrDo,
+	MsPacman,
+	NameThisGame,
+	Othello,
+	Pacman,
+	Phoenix,
+	Pitfall,
+	Pitfall2,
+	Pong,
+	Pooyan,
+	PrivateEye,
+	Qbert,
+	Riverraid,
+	RoadRunner,
+	Robotank,
+	Seaquest,
+	SirLancelot,
+	Skiing,
+	Solaris,
+	SpaceInvaders,
+	SpaceWar,
+	StarGunner,
+	Superman,
+	Surround,
+	Tennis,
+	Tetris,
+	TicTacToe3d,
+	TimePilot,
+	Trondead,
+	Turmoil,
+	Tutankham,
+	UpNDown,
+	Venture,
+	VideoCheckers,
+	VideoChess,
+	VideoCube,
+	VideoPinball,
+	Warlords,
+	WizardOfWor,
+	WordZapper,
+	YarsRevenge,
+	Zaxxon,
+}
+impl BundledRom {
+	/// Every bundled ROM, in alphabetical order.
+	pub const ALL: [BundledRom; 108] = [
+		BundledRom::Adventure,
+		BundledRom::AirRaid,
+		BundledRom::Alien,
+		BundledRom::Amidar,
+		BundledRom::Assault,
+		BundledRom::Asterix,
+		BundledRom::Asteroids,
+		BundledRom::Atlantis,
+		BundledRom::Atlantis2,
+		BundledRom::Backgammon,
+		BundledRom::BankHeist,
+		BundledRom::BasicMath,
+		BundledRom::BattleZone,
+		BundledRom::BeamRider,
+		BundledRom::Berzerk,
+		BundledRom::Blackjack,
+		BundledRom::Bowling,
+		BundledRom::Boxing,
+		BundledRom::Breakout,
+		BundledRom::Carnival,
+		BundledRom::Casino,
+		BundledRom::Centipede,
+		BundledRom::ChopperCommand,
+		BundledRom::Combat,
+		BundledRom::CrazyClimber,
+		BundledRom::Crossbow,
+		BundledRom::Darkchambers,
+		BundledRom::Defender,
+		BundledRom::DemonAttack,
+		BundledRom::DonkeyKong,
+		BundledRom::DoubleDunk,
+		BundledRom::Earthworld,
+		BundledRom::ElevatorAction,
+		BundledRom::Enduro,
+		BundledRom::Entombed,
+		BundledRom::Et,
+		BundledRom::FishingDerby,
+		BundledRom::FlagCapture,
+		BundledRom::Freeway,
+		BundledRom::Frogger,
+		BundledRom::Frostbite,
+		BundledRom::Galaxian,
+		BundledRom::Gopher,
+		BundledRom::Gravitar,
+		BundledRom::Hangman,
+		BundledRom::HauntedHouse,
+		BundledRom::Hero,
+		BundledRom::HumanCannonball,
+		BundledRom::IceHockey,
+		BundledRom::Jamesbond,
+		BundledRom::JourneyEscape,
+		BundledRom::Joust,
+		BundledRom::Kaboom,
+		BundledRom::Kangaroo,
+		BundledRom::KeystoneKapers,
+		BundledRom::KingKong,
+		BundledRom::Klax,
+		BundledRom::Koolaid,
+		BundledRom::Krull,
+		BundledRom::KungFuMaster,
+		BundledRom::LaserGates,
+		BundledRom::LostLuggage,
+		BundledRom::MarioBros,
+		BundledRom::MazeCraze,
+		BundledRom::MiniatureGolf,
+		BundledRom::MontezumaRevenge,
+		BundledRom::MrDo,
+		BundledRom::MsPacman,
+		BundledRom::NameThisGame,
+		BundledRom::Othello,
+		BundledRom::Pacman,
+		BundledRom::Phoenix,
+		BundledRom::Pitfall,
+		BundledRom::Pitfall2,
+		BundledRom::Pong,
+		BundledRom::Pooyan,
+		BundledRom::PrivateEye,
+		BundledRom::Qbert,
+		BundledRom::Riverraid,
+		BundledRom::RoadRunner,
+		BundledRom::Robotank,
+		BundledRom::Seaquest,
+		BundledRom::SirLancelot,
+		BundledRom::Skiing,
+		BundledRom::Solaris,
+		BundledRom::SpaceInvaders,
+		BundledRom::SpaceWar,
+		BundledRom::StarGunner,
+		BundledRom::Superman,
+		BundledRom::Surround,
+		BundledRom::Tennis,
+		BundledRom::Tetris,
+		BundledRom::TicTacToe3d,
+		BundledRom::TimePilot,
+		BundledRom::Trondead,
+		BundledRom::Turmoil,
+		BundledRom::Tutankham,
+		BundledRom::UpNDown,
+		BundledRom::Venture,
+		BundledRom::VideoCheckers,
+		BundledRom::VideoChess,
+		BundledRom::VideoCube,
+		BundledRom::VideoPinball,
+		BundledRom::Warlords,
+		BundledRom::WizardOfWor,
+		BundledRom::WordZapper,
+		BundledRom::YarsRevenge,
+		BundledRom::Zaxxon,
+	];
+
+	/// The 5 games of the Atari-5 subset, whose scores predict the score on the full Atari-57 benchmark.
+	///
+	/// These are the ROMs enabled by the `atari5` feature.
+	pub const ATARI_5: [BundledRom; 5] = [
+		BundledRom::BattleZone,
+		BundledRom::DoubleDunk,
+		BundledRom::NameThisGame,
+		BundledRom::Phoenix,
+		BundledRom::Qbert,
+	];
+
+	/// The 57 games of the Atari-57 benchmark, as used by e.g. DQN and Agent57.
+	///
+	/// These are the ROMs enabled by the `atari57` feature.
+	pub const ATARI_57: [BundledRom; 57] = [
+		BundledRom::Alien,
+		BundledRom::Amidar,
+		BundledRom::Assault,
+		BundledRom::Asterix,
+		BundledRom::Asteroids,
+		BundledRom::Atlantis,
+		BundledRom::BankHeist,
+		BundledRom::BattleZone,
+		BundledRom::BeamRider,
+		BundledRom::Berzerk,
+		BundledRom::Bowling,
+		BundledRom::Boxing,
+		BundledRom::Breakout,
+		BundledRom::Centipede,
+		BundledRom::ChopperCommand,
+		BundledRom::CrazyClimber,
+		BundledRom::Defender,
+		BundledRom::DemonAttack,
+		BundledRom::DoubleDunk,
+		BundledRom::Enduro,
+		BundledRom::FishingDerby,
+		BundledRom::Freeway,
+		BundledRom::Frostbite,
+		BundledRom::Gopher,
+		BundledRom::Gravitar,
+		BundledRom::Hero,
+		BundledRom::IceHockey,
+		BundledRom::Jamesbond,
+		BundledRom::Kangaroo,
+		BundledRom::Krull,
+		BundledRom::KungFuMaster,
+		BundledRom::MontezumaRevenge,
+		BundledRom::MsPacman,
+		BundledRom::NameThisGame,
+		BundledRom::Phoenix,
+		BundledRom::Pitfall,
+		BundledRom::Pong,
+		BundledRom::PrivateEye,
+		BundledRom::Qbert,
+		BundledRom::Riverraid,
+		BundledRom::RoadRunner,
+		BundledRom::Robotank,
+		BundledRom::Seaquest,
+		BundledRom::Skiing,
+		BundledRom::Solaris,
+		BundledRom::SpaceInvaders,
+		BundledRom::StarGunner,
+		BundledRom::Surround,
+		BundledRom::Tennis,
+		BundledRom::TimePilot,
+		BundledRom::Tutankham,
+		BundledRom::UpNDown,
+		BundledRom::Venture,
+		BundledRom::VideoPinball,
+		BundledRom::WizardOfWor,
+		BundledRom::YarsRevenge,
+		BundledRom::Zaxxon,
+	];
+
+	/// Returns the filename that the ROM should be named, in order for the ALE to pick up on it and
+	/// use the correct settings.
+	pub fn filename(&self) -> &'static str {
+		use BundledRom::*;
+		match self {
+			Adventure => "adventure.bin",
+			AirRaid => "air_raid.bin",
+			Alien => "alien.bin",
+			Amidar => "amidar.bin",
+			Assault => "assault.bin",
+			Asterix => "asterix.bin",
+			Asteroids => "asteroids.bin",
+			Atlantis => "atlantis.bin",
+			Atlantis2 => "atlantis2.bin",
+			Backgammon => "backgammon.bin",
+			BankHeist => "bank_heist.bin",
+			BasicMath => "basic_math.bin",
+			BattleZone => "battle_zone.bin",
+			BeamRider => "beam_rider.bin",
+			Berzerk => "berzerk.bin",
+			Blackjack => "blackjack.bin",
+			Bowling => "bowling.bin",
+			Boxing => "boxing.bin",
+			Breakout => "breakout.bin",
+			Carnival => "carnival.bin",
+			Casino => "casino.bin",
+			Centipede => "centipede.bin",
+			ChopperCommand => "chopper_command.bin",
+			Combat => "combat.bin",
+			CrazyClimber => "crazy_climber.bin",
+			Crossbow => "crossbow.bin",
+			Darkchambers => "darkchambers.bin",
+			Defender => "defender.bin",
+			DemonAttack => "demon_attack.bin",
+			DonkeyKong => "donkey_kong.bin",
+			DoubleDunk => "double_dunk.bin",
+			Earthworld => "earthworld.bin",
+			ElevatorAction => "elevator_action.bin",
+			Enduro => "enduro.bin",
+			Entombed => "entombed.bin",
+			Et => "et.bin",
+			FishingDerby => "fishing_derby.bin",
+			FlagCapture => "flag_capture.bin",
+			Freeway => "freeway.bin",
+			Frogger => "frogger.bin",
+			Frostbite => "frostbite.bin",
+			Galaxian => "galaxian.bin",
+			Gopher => "gopher.bin",
+			Gravitar => "gravitar.bin",
+			Hangman => "hangman.bin",
+			HauntedHouse => "haunted_house.bin",
+			Hero => "hero.bin",
+			HumanCannonball => "human_cannonball.bin",
+			IceHockey => "ice_hockey.bin",
+			Jamesbond => "jamesbond.bin",
+			JourneyEscape => "journey_escape.bin",
+			Joust => "joust.bin",
+			Kaboom => "kaboom.bin",
+			Kangaroo => "kangaroo.bin",
+			KeystoneKapers => "keystone_kapers.bin",
+			KingKong => "king_kong.bin",
+			Klax => "klax.bin",
+			Koolaid => "koolaid.bin",
+			Krull => "krull.bin",
+			KungFuMaster => "kung_fu_master.bin",
+			LaserGates => "laser_gates.bin",
+			LostLuggage => "lost_luggage.bin",
+			MarioBros => "mario_bros.bin",
+			MazeCraze => "maze_craze.bin",
+			MiniatureGolf => "miniature_golf.bin",
+			MontezumaRevenge => "montezuma_revenge.bin",
+			MrDo => "mr_do.bin",
+			MsPacman => "ms_pacman.bin",
+			NameThisGame => "name_this_game.bin",
+			Othello => "othello.bin",
+			Pacman => "pacman.bin",
+			Phoenix => "phoenix.bin",
+			Pitfall => "pitfall.bin",
+			Pitfall2 => "pitfall2.bin",
+			Pong => "pong.bin",
+			Pooyan => "pooyan.bin",
+			PrivateEye => "private_eye.bin",
+			Qbert => "qbert.bin",
+			Riverraid => "riverraid.bin",
+			RoadRunner => "road_runner.bin",
+			Robotank => "robotank.bin",
+			Seaquest => "seaquest.bin",
+			SirLancelot => "sir_lancelot.bin",
+			Skiing => "skiing.bin",
+			Solaris => "solaris.bin",
+			SpaceInvaders => "space_invaders.bin",
+			SpaceWar => "space_war.bin",
+			StarGunner => "star_gunner.bin",
+			Superman => "superman.bin",
+			Surround => "surround.bin",
+			Tennis => "tennis.bin",
+			Tetris => "tetris.bin",
+			TicTacToe3d => "tic_tac_toe_3d.bin",
+			TimePilot => "time_pilot.bin",
+			Trondead => "trondead.bin",
+			Turmoil => "turmoil.bin",
+			Tutankham => "tutankham.bin",
+			UpNDown => "up_n_down.bin",
+			Venture => "venture.bin",
+			VideoCheckers => "video_checkers.bin",
+			VideoChess => "video_chess.bin",
+			VideoCube => "video_cube.bin",
+			VideoPinball => "video_pinball.bin",
+			Warlords => "warlords.bin",
+			WizardOfWor => "wizard_of_wor.bin",
+			WordZapper => "word_zapper.bin",
+			YarsRevenge => "yars_revenge.bin",
+			Zaxxon => "zaxxon.bin",
+		}
+	}
+
+	/// Returns the human readable title of the game, e.g. `"Ms. Pac-Man"`.
+	pub fn title(&self) -> &'static str {
+		use BundledRom::*;
+		match self {
+			Adventure => "Adventure",
+			AirRaid => "Air Raid",
+			Alien => "Alien",
+			Amidar => "Amidar",
+			Assault => "Assault",
+			Asterix => "Asterix",
+			Asteroids => "Asteroids",
+			Atlantis => "Atlantis",
+			Atlantis2 => "Atlantis II",
+			Backgammon => "Backgammon",
+			BankHeist => "Bank Heist",
+			BasicMath => "Basic Math",
+			BattleZone => "Battle Zone",
+			BeamRider => "Beam Rider",
+			Berzerk => "Berzerk",
+			Blackjack => "Blackjack",
+			Bowling => "Bowling",
+			Boxing => "Boxing",
+			Breakout => "Breakout",
+			Carnival => "Carnival",
+			Casino => "Casino",
+			Centipede => "Centipede",
+			ChopperCommand => "Chopper Command",
+			Combat => "Combat",
+			CrazyClimber => "Crazy Climber",
+			Crossbow => "Crossbow",
+			Darkchambers => "Dark Chambers",
+			Defender => "Defender",
+			DemonAttack => "Demon Attack",
+			DonkeyKong => "Donkey Kong",
+			DoubleDunk => "Double Dunk",
+			Earthworld => "Earthworld",
+			ElevatorAction => "Elevator Action",
+			Enduro => "Enduro",
+			Entombed => "Entombed",
+			Et => "E.T. the Extra-Terrestrial",
+			FishingDerby => "Fishing Derby",
+			FlagCapture => "Flag Capture",
+			Freeway => "Freeway",
+			Frogger => "Frogger",
+			Frostbite => "Frostbite",
+			Galaxian => "Galaxian",
+			Gopher => "Gopher",
+			Gravitar => "Gravitar",
+			Hangman => "Hangman",
+			HauntedHouse => "Haunted House",
+			Hero => "H.E.R.O.",
+			HumanCannonball => "Human Cannonball",
+			IceHockey => "Ice Hockey",
+			Jamesbond => "James Bond 007",
+			JourneyEscape => "Journey Escape",
+			Joust => "Joust",
+			Kaboom => "Kaboom",
+			Kangaroo => "Kangaroo",
+			KeystoneKapers => "Keystone Kapers",
+			KingKong => "King Kong",
+			Klax => "Klax",
+			Koolaid => "Kool-Aid Man",
+			Krull => "Krull",
+			KungFuMaster => "Kung-Fu Master",
+			LaserGates => "Laser Gates",
+			LostLuggage => "Lost Luggage",
+			MarioBros => "Mario Bros.",
+			MazeCraze => "Maze Craze",
+			MiniatureGolf => "Miniature Golf",
+			MontezumaRevenge => "Montezuma's Revenge",
+			MrDo => "Mr. Do!",
+			MsPacman => "Ms. Pac-Man",
+			NameThisGame => "Name This Game",
+			Othello => "Othello",
+			Pacman => "Pac-Man",
+			Phoenix => "Phoenix",
+			Pitfall => "Pitfall",
+			Pitfall2 => "Pitfall II: Lost Caverns",
+			Pong => "Pong",
+			Pooyan => "Pooyan",
+			PrivateEye => "Private Eye",
+			Qbert => "Q*bert",
+			Riverraid => "River Raid",
+			RoadRunner => "Road Runner",
+			Robotank => "Robot Tank",
+			Seaquest => "Seaquest",
+			SirLancelot => "Sir Lancelot",
+			Skiing => "Skiing",
+			Solaris => "Solaris",
+			SpaceInvaders => "Space Invaders",
+			SpaceWar => "Space War",
+			StarGunner => "Star Gunner",
+			Superman => "Superman",
+			Surround => "Surround",
+			Tennis => "Tennis",
+			Tetris => "Tetris",
+			TicTacToe3d => "3-D Tic-Tac-Toe",
+			TimePilot => "Time Pilot",
+			Trondead => "TRON: Deadly Discs",
+			Turmoil => "Turmoil",
+			Tutankham => "Tutankham",
+			UpNDown => "Up 'n Down",
+			Venture => "Venture",
+			VideoCheckers => "Video Checkers",
+			VideoChess => "Video Chess",
+			VideoCube => "Video Cube",
+			VideoPinball => "Video Pinball",
+			Warlords => "Warlords",
+			WizardOfWor => "Wizard of Wor",
+			WordZapper => "Word Zapper",
+			YarsRevenge => "Yars' Revenge",
+			Zaxxon => "Zaxxon",
+		}
+	}
+
+	/// Returns the MD5 checksum of the ROM, as a lowercase hex string. This is what the ALE uses to identify games.
+	pub fn md5(&self) -> &'static str {
+		use BundledRom::*;
+		match self {
+			Adventure => "4b27f5397c442d25f0c418ccdacf1926",
+			AirRaid => "35be55426c1fec32dfb503b4f0651572",
+			Alien => "f1a0a23e6464d954e3a9579c4ccd01c8",
+			Amidar => "acb7750b4d0c4bd34969802a7deb2990",
+			Assault => "de78b3a064d374390ac0710f95edde92",
+			Asterix => "89a68746eff7f266bbf08de2483abe55",
+			Asteroids => "ccbd36746ed4525821a8083b0d6d2c2c",
+			Atlantis => "9ad36e699ef6f45d9eb6c4cf90475c9f",
+			Atlantis2 => "826481f6fc53ea47c9f272f7050eedf7",
+			Backgammon => "8556b42aa05f94bc29ff39c39b11bff4",
+			BankHeist => "00ce0bdd43aed84a983bef38fe7f5ee3",
+			BasicMath => "819aeeb9a2e11deb54e6de334f843894",
+			BattleZone => "41f252a66c6301f1e8ab3612c19bc5d4",
+			BeamRider => "79ab4123a83dc11d468fb2108ea09e2e",
+			Berzerk => "136f75c4dd02c29283752b7e5799f978",
+			Blackjack => "0a981c03204ac2b278ba392674682560",
+			Bowling => "c9b7afad3bfd922e006a6bfc1d4f3fe7",
+			Boxing => "c3ef5c4653212088eda54dc91d787870",
+			Breakout => "f34f08e5eb96e500e851a80be3277a56",
+			Carnival => "028024fb8e5e5f18ea586652f9799c96",
+			Casino => "b816296311019ab69a21cb9e9e235d12",
+			Centipede => "91c2098e88a6b13f977af8c003e0bca5",
+			ChopperCommand => "c1cb228470a87beb5f36e90ac745da26",
+			Combat => "0ef64cdbecccb7049752a3de0b7ade14",
+			CrazyClimber => "55ef7b65066428367844342ed59f956c",
+			Crossbow => "8cd26dcf249456fe4aeb8db42d49df74",
+			Darkchambers => "106855474c69d08c8ffa308d47337269",
+			Defender => "0f643c34e40e3f1daafd9c524d3ffe64",
+			DemonAttack => "f0e0addc07971561ab80d9abe1b8d333",
+			DonkeyKong => "36b20c427975760cb9cf4a47e41369e4",
+			DoubleDunk => "368d88a6c071caba60b4f778615aae94",
+			Earthworld => "5aea9974b975a6a844e6df10d2b861c4",
+			ElevatorAction => "71f8bacfbdca019113f3f0801849057e",
+			Enduro => "94b92a882f6dbaa6993a46e2dcc58402",
+			Entombed => "6b683be69f92958abe0e2a9945157ad5",
+			Et => "615a3bf251a38eb6638cdc7ffbde5480",
+			FishingDerby => "b8865f05676e64f3bec72b9defdacfa7",
+			FlagCapture => "30512e0e83903fc05541d2f6a6a62654",
+			Freeway => "8e0ab801b1705a740b476b7f588c6d16",
+			Frogger => "081e2c114c9c20b61acf25fc95c71bf4",
+			Frostbite => "4ca73eb959299471788f0b685c3ba0b5",
+			Galaxian => "211774f4c5739042618be8ff67351177",
+			Gopher => "c16c79aad6272baffb8aae9a7fff0864",
+			Gravitar => "8ac18076d01a6b63acf6e2cab4968940",
+			Hangman => "f16c709df0a6c52f47ff52b9d95b7d8d",
+			HauntedHouse => "f0a6e99f5875891246c3dbecbf2d2cea",
+			Hero => "fca4a5be1251927027f2c24774a02160",
+			HumanCannonball => "7972e5101fa548b952d852db24ad6060",
+			IceHockey => "a4c08c4994eb9d24fb78be1793e82e26",
+			Jamesbond => "e51030251e440cffaab1ac63438b44ae",
+			JourneyEscape => "718ae62c70af4e5fd8e932fee216948a",
+			Joust => "3276c777cbe97cdd2b4a63ffc16b7151",
+			Kaboom => "5428cdfada281c569c74c7308c7f2c26",
+			Kangaroo => "4326edb70ff20d0ee5ba58fa5cb09d60",
+			KeystoneKapers => "6c1f3f2e359dbf55df462ccbcdd2f6bf",
+			KingKong => "0dd4c69b5f9a7ae96a7a08329496779a",
+			Klax => "eed9eaf1a0b6a2b9bc4c8032cb43e3fb",
+			Koolaid => "534e23210dd1993c828d944c6ac4d9fb",
+			Krull => "4baada22435320d185c95b7dd2bcdb24",
+			KungFuMaster => "5b92a93b23523ff16e2789b820e2a4c5",
+			LaserGates => "8e4cd60d93fcde8065c1a2b972a26377",
+			LostLuggage => "2d76c5d1aad506442b9e9fb67765e051",
+			MarioBros => "e908611d99890733be31733a979c62d8",
+			MazeCraze => "ed2218b3075d15eaa34e3356025ccca3",
+			MiniatureGolf => "df62a658496ac98a3aa4a6ee5719c251",
+			MontezumaRevenge => "3347a6dd59049b15a38394aa2dafa585",
+			MrDo => "aa7bb54d2c189a31bb1fa20099e42859",
+			MsPacman => "87e79cd41ce136fd4f72cc6e2c161bee",
+			NameThisGame => "36306070f0c90a72461551a7a4f3a209",
+			Othello => "113cd09c9771ac278544b7e90efe7df2",
+			Pacman => "fc2233fc116faef0d3c31541717ca2db",
+			Phoenix => "7e52a95074a66640fcfde124fffd491a",
+			Pitfall => "3e90cf23106f2e08b2781e41299de556",
+			Pitfall2 => "6d842c96d5a01967be9680080dd5be54",
+			Pong => "60e0ea3cbe0913d39803477945e9e5ec",
+			Pooyan => "4799a40b6e889370b7ee55c17ba65141",
+			PrivateEye => "ef3a4f64b6494ba770862768caf04b86",
+			Qbert => "484b0076816a104875e00467d431c2d2",
+			Riverraid => "393948436d1f4cc3192410bb918f9724",
+			RoadRunner => "ce5cc62608be2cd3ed8abd844efb8919",
+			Robotank => "4f618c2429138e0280969193ed6c107e",
+			Seaquest => "240bfbac5163af4df5ae713985386f92",
+			SirLancelot => "dd0cbe5351551a538414fb9e37fc56e8",
+			Skiing => "b76fbadc8ffb1f83e2ca08b6fb4d6c9f",
+			Solaris => "e72eb8d4410152bdcb69e7fba327b420",
+			SpaceInvaders => "72ffbef6504b75e69ee1045af9075f66",
+			SpaceWar => "b702641d698c60bcdc922dbd8c9dd49c",
+			StarGunner => "a3c1c70024d7aabb41381adbfb6d3b25",
+			Superman => "a9531c763077464307086ec9a1fd057d",
+			Surround => "4d7517ae69f95cfbc053be01312b7dba",
+			Tennis => "42cdd6a9e42a3639e190722b8ea3fc51",
+			Tetris => "b0e1ee07fbc73493eac5651a52f90f00",
+			TicTacToe3d => "0db4f4150fecf77e4ce72ca4d04c052f",
+			TimePilot => "fc2104dd2dadf9a6176c1c1c8f87ced9",
+			Trondead => "fb27afe896e7c928089307b32e5642ee",
+			Turmoil => "7a5463545dfb2dcfdafa6074b2f2c15e",
+			Tutankham => "085322bae40d904f53bdcc56df0593fc",
+			UpNDown => "a499d720e7ee35c62424de882a3351b6",
+			Venture => "3e899eba0ca8cd2972da1ae5479b4f0d",
+			VideoCheckers => "539d26b6e9df0da8e7465f0f5ad863b7",
+			VideoChess => "f0b7db930ca0e548c41a97160b9f6275",
+			VideoCube => "3f540a30fdee0b20aed7288e4a5ea528",
+			VideoPinball => "107cc025334211e6d29da0b6be46aec7",
+			Warlords => "cbe5a166550a8129a5e6d374901dffad",
+			WizardOfWor => "7e8aa18bc9502eb57daaf5e7c1e94da7",
+			WordZapper => "ec3beb6d8b5689e867bafb5d5f507491",
+			YarsRevenge => "c5930d0e8cdae3e037349bfa08e871be",
+			Zaxxon => "eea0da9b987d661264cce69a7c13c3bd",
+		}
+	}
+
+	/// Returns the cargo feature that bundles this ROM, e.g. `"rom-ms-pacman"`.
+	pub fn feature(&self) -> &'static str {
+		use BundledRom::*;
+		match self {
+			Adventure => "rom-adventure",
+			AirRaid => "rom-air-raid",
+			Alien => "rom-alien",
+			Amidar => "rom-amidar",
+			Assault => "rom-assault",
+			Asterix => "rom-asterix",
+			Asteroids => "rom-asteroids",
+			Atlantis => "rom-atlantis",
+			Atlantis2 => "rom-atlantis2",
+			Backgammon => "rom-backgammon",
+			BankHeist => "rom-bank-heist",
+			BasicMath => "rom-basic-math",
+			BattleZone => "rom-battle-zone",
+			BeamRider => "rom-beam-rider",
+			Berzerk => "rom-berzerk",
+			Blackjack => "rom-blackjack",
+			Bowling => "rom-bowling",
+			Boxing => "rom-boxing",
+			Breakout => "rom-breakout",
+			Carnival => "rom-carnival",
+			Casino => "rom-casino",
+			Centipede => "rom-centipede",
+			ChopperCommand => "rom-chopper-command",
+			Combat => "rom-combat",
+			CrazyClimber => "rom-crazy-climber",
+			Crossbow => "rom-crossbow",
+			Darkchambers => "rom-darkchambers",
+			Defender => "rom-defender",
+			DemonAttack => "rom-demon-attack",
+			DonkeyKong => "rom-donkey-kong",
+			DoubleDunk => "rom-double-dunk",
+			Earthworld => "rom-earthworld",
+			ElevatorAction => "rom-elevator-action",
+			Enduro => "rom-enduro",
+			Entombed => "rom-entombed",
+			Et => "rom-et",
+			FishingDerby => "rom-fishing-derby",
+			FlagCapture => "rom-flag-capture",
+			Freeway => "rom-freeway",
+			Frogger => "rom-frogger",
+			Frostbite => "rom-frostbite",
+			Galaxian => "rom-galaxian",
+			Gopher => "rom-gopher",
+			Gravitar => "rom-gravitar",
+			Hangman => "rom-hangman",
+			HauntedHouse => "rom-haunted-house",
+			Hero => "rom-hero",
+			HumanCannonball => "rom-human-cannonball",
+			IceHockey => "rom-ice-hockey",
+			Jamesbond => "rom-jamesbond",
+			JourneyEscape => "rom-journey-escape",
+			Joust => "rom-joust",
+			Kaboom => "rom-kaboom",
+			Kangaroo => "rom-kangaroo",
+			KeystoneKapers => "rom-keystone-kapers",
+			KingKong => "rom-king-kong",
+			Klax => "rom-klax",
+			Koolaid => "rom-koolaid",
+			Krull => "rom-krull",
+			KungFuMaster => "rom-kung-fu-master",
+			LaserGates => "rom-laser-gates",
+			LostLuggage => "rom-lost-luggage",
+			MarioBros => "rom-mario-bros",
+			MazeCraze => "rom-maze-craze",
+			MiniatureGolf => "rom-miniature-golf",
+			MontezumaRevenge => "rom-montezuma-revenge",
+			MrDo => "rom-mr-do",
+			MsPacman => "rom-ms-pacman",
+			NameThisGame => "rom-name-this-game",
+			Othello => "rom-othello",
+			Pacman => "rom-pacman",
+			Phoenix => "rom-phoenix",
+			Pitfall => "rom-pitfall",
+			Pitfall2 => "rom-pitfall2",
+			Pong => "rom-pong",
+			Pooyan => "rom-pooyan",
+			PrivateEye => "rom-private-eye",
+			Qbert => "rom-qbert",
+			Riverraid => "rom-riverraid",
+			RoadRunner => "rom-road-runner",
+			Robotank => "rom-robotank",
+			Seaquest => "rom-seaquest",
+			SirLancelot => "rom-sir-lancelot",
+			Skiing => "rom-skiing",
+			Solaris => "rom-solaris",
+			SpaceInvaders => "rom-space-invaders",
+			SpaceWar => "rom-space-war",
+			StarGunner => "rom-star-gunner",
+			Superman => "rom-superman",
+			Surround => "rom-surround",
+			Tennis => "rom-tennis",
+			Tetris => "rom-tetris",
+			TicTacToe3d => "rom-tic-tac-toe-3d",
+			TimePilot => "rom-time-pilot",
+			Trondead => "rom-trondead",
+			Turmoil => "rom-turmoil",
+			Tutankham => "rom-tutankham",
+			UpNDown => "rom-up-n-down",
+			Venture => "rom-venture",
+			VideoCheckers => "rom-video-checkers",
+			VideoChess => "rom-video-chess",
+			VideoCube => "rom-video-cube",
+			VideoPinball => "rom-video-pinball",
+			Warlords => "rom-warlords",
+			WizardOfWor => "rom-wizard-of-wor",
+			WordZapper => "rom-word-zapper",
+			YarsRevenge => "rom-yars-revenge",
+			Zaxxon => "rom-zaxxon",
+		}
+	}
+
+	/// Returns the ROM's data if it was included uncompressed in the binary.
+	#[cfg(not(feature = "compressed-roms"))]
+	pub(super) fn included_data(&self) -> Option<&'static [u8]> {
+		#[allow(unused_imports)]
+		use BundledRom::*;
+		match self {
+			#[cfg(all(feature = "rom-adventure", not(feature = "no-bundled-roms")))]
+			Adventure => Some(include_bytes!("../../roms/adventure.bin")),
+			#[cfg(all(feature = "rom-air-raid", not(feature = "no-bundled-roms")))]
+			AirRaid => Some(include_bytes!("../../roms/air_raid.bin")),
+			#[cfg(all(feature = "rom-alien", not(feature = "no-bundled-roms")))]
+			Alien => Some(include_bytes!("../../roms/alien.bin")),
+			#[cfg(all(feature = "rom-amidar", not(feature = "no-bundled-roms")))]
+			Amidar => Some(include_bytes!("../../roms/amidar.bin")),
+			#[cfg(all(feature = "rom-assault", not(feature = "no-bundled-roms")))]
+			Assault => Some(include_bytes!("../../roms/assault.bin")),
+			#[cfg(all(feature = "rom-asterix", not(feature = "no-bundled-roms")))]
+			Asterix => Some(include_bytes!("../../roms/asterix.bin")),
+			#[cfg(all(feature = "rom-asteroids", not(feature = "no-bundled-roms")))]
+			Asteroids => Some(include_bytes!("../../roms/asteroids.bin")),
+			#[cfg(all(feature = "rom-atlantis", not(feature = "no-bundled-roms")))]
+			Atlantis => Some(include_bytes!("../../roms/atlantis.bin")),
+			#[cfg(all(feature = "rom-atlantis2", not(feature = "no-bundled-roms")))]
+			Atlantis2 => Some(include_bytes!("../../roms/atlantis2.bin")),
+			#[cfg(all(feature = "rom-backgammon", not(feature = "no-bundled-roms")))]
+			Backgammon => Some(include_bytes!("../../roms/backgammon.bin")),
+			#[cfg(all(feature = "rom-bank-heist", not(feature = "no-bundled-roms")))]
+			BankHeist => Some(include_bytes!("../../roms/bank_heist.bin")),
+			#[cfg(all(feature = "rom-basic-math", not(feature = "no-bundled-roms")))]
+			BasicMath => Some(include_bytes!("../../roms/basic_math.bin")),
+			#[cfg(all(feature = "rom-battle-zone", not(feature = "no-bundled-roms")))]
+			BattleZone => Some(include_bytes!("../../roms/battle_zone.bin")),
+			#[cfg(all(feature = "rom-beam-rider", not(feature = "no-bundled-roms")))]
+			BeamRider => Some(include_bytes!("../../roms/beam_rider.bin")),
+			#[cfg(all(feature = "rom-berzerk", not(feature = "no-bundled-roms")))]
+			Berzerk => Some(include_bytes!("../../roms/berzerk.bin")),
+			#[cfg(all(feature = "rom-blackjack", not(feature = "no-bundled-roms")))]
+			Blackjack => Some(include_bytes!("../../roms/blackjack.bin")),
+			#[cfg(all(feature = "rom-bowling", not(feature = "no-bundled-roms")))]
+			Bowling => Some(include_bytes!("../../roms/bowling.bin")),
+			#[cfg(all(feature = "rom-boxing", not(feature = "no-bundled-roms")))]
+			Boxing => Some(include_bytes!("../../roms/boxing.bin")),
+			#[cfg(all(feature = "rom-breakout", not(feature = "no-bundled-roms")))]
+			Breakout => Some(include_bytes!("../../roms/breakout.bin")),
+			#[cfg(all(feature = "rom-carnival", not(feature = "no-bundled-roms")))]
+			Carnival => Some(include_bytes!("../../roms/carnival.bin")),
+			#[cfg(all(feature = "rom-casino", not(feature = "no-bundled-roms")))]
+			Casino => Some(include_bytes!("../../roms/casino.bin")),
+			#[cfg(all(feature = "rom-centipede", not(feature = "no-bundled-roms")))]
+			Centipede => Some(include_bytes!("../../roms/centipede.bin")),
+			#[cfg(all(feature = "rom-chopper-command", not(feature = "no-bundled-roms")))]
+			ChopperCommand => Some(include_bytes!("../../roms/chopper_command.bin")),
+			#[cfg(all(feature = "rom-combat", not(feature = "no-bundled-roms")))]
+			Combat => Some(include_bytes!("../../roms/combat.bin")),
+			#[cfg(all(feature = "rom-crazy-climber", not(feature = "no-bundled-roms")))]
+			CrazyClimber => Some(include_bytes!("../../roms/crazy_climber.bin")),
+			#[cfg(all(feature = "rom-crossbow", not(feature = "no-bundled-roms")))]
+			Crossbow => Some(include_bytes!("../../roms/crossbow.bin")),
+			#[cfg(all(feature = "rom-darkchambers", not(feature = "no-bundled-roms")))]
+			Darkchambers => Some(include_bytes!("../../roms/darkchambers.bin")),
+			#[cfg(all(feature = "rom-defender", not(feature = "no-bundled-roms")))]
+			Defender => Some(include_bytes!("../../roms/defender.bin")),
+			#[cfg(all(feature = "rom-demon-attack", not(feature = "no-bundled-roms")))]
+			DemonAttack => Some(include_bytes!("../../roms/demon_attack.bin")),
+			#[cfg(all(feature = "rom-donkey-kong", not(feature = "no-bundled-roms")))]
+			DonkeyKong => Some(include_bytes!("../../roms/donkey_kong.bin")),
+			#[cfg(all(feature = "rom-double-dunk", not(feature = "no-bundled-roms")))]
+			DoubleDunk => Some(include_bytes!("../../roms/double_dunk.bin")),
+			#[cfg(all(feature = "rom-earthworld", not(feature = "no-bundled-roms")))]
+			Earthworld => Some(include_bytes!("../../roms/earthworld.bin")),
+			#[cfg(all(feature = "rom-elevator-action", not(feature = "no-bundled-roms")))]
+			ElevatorAction => Some(include_bytes!("../../roms/elevator_action.bin")),
+			#[cfg(all(feature = "rom-enduro", not(feature = "no-bundled-roms")))]
+			Enduro => Some(include_bytes!("../../roms/enduro.bin")),
+			#[cfg(all(feature = "rom-entombed", not(feature = "no-bundled-roms")))]
+			Entombed => Some(include_bytes!("../../roms/entombed.bin")),
+			#[cfg(all(feature = "rom-et", not(feature = "no-bundled-roms")))]
+			Et => Some(include_bytes!("../../roms/et.bin")),
+			#[cfg(all(feature = "rom-fishing-derby", not(feature = "no-bundled-roms")))]
+			FishingDerby => Some(include_bytes!("../../roms/fishing_derby.bin")),
+			#[cfg(all(feature = "rom-flag-capture", not(feature = "no-bundled-roms")))]
+			FlagCapture => Some(include_bytes!("../../roms/flag_capture.bin")),
+			#[cfg(all(feature = "rom-freeway", not(feature = "no-bundled-roms")))]
+			Freeway => Some(include_bytes!("../../roms/freeway.bin")),
+			#[cfg(all(feature = "rom-frogger", not(feature = "no-bundled-roms")))]
+			Frogger => Some(include_bytes!("../../roms/frogger.bin")),
+			#[cfg(all(feature = "rom-frostbite", not(feature = "no-bundled-roms")))]
+			Frostbite => Some(include_bytes!("../../roms/frostbite.bin")),
+			#[cfg(all(feature = "rom-galaxian", not(feature = "no-bundled-roms")))]
+			Galaxian => Some(include_bytes!("../../roms/galaxian.bin")),
+			#[cfg(all(feature = "rom-gopher", not(feature = "no-bundled-roms")))]
+			Gopher => Some(include_bytes!("../../roms/gopher.bin")),
+			#[cfg(all(feature = "rom-gravitar", not(feature = "no-bundled-roms")))]
+			Gravitar => Some(include_bytes!("../../roms/gravitar.bin")),
+			#[cfg(all(feature = "rom-hangman", not(feature = "no-bundled-roms")))]
+			Hangman => Some(include_bytes!("../../roms/hangman.bin")),
+			#[cfg(all(feature = "rom-haunted-house", not(feature = "no-bundled-roms")))]
+			HauntedHouse => Some(include_bytes!("../../roms/haunted_house.bin")),
+			#[cfg(all(feature = "rom-hero", not(feature = "no-bundled-roms")))]
+			Hero => Some(include_bytes!("../../roms/hero.bin")),
+			#[cfg(all(feature = "rom-human-cannonball", not(feature = "no-bundled-roms")))]
+			HumanCannonball => Some(include_bytes!("../../roms/human_cannonball.bin")),
+			#[cfg(all(feature = "rom-ice-hockey", not(feature = "no-bundled-roms")))]
+			IceHockey => Some(include_bytes!("../../roms/ice_hockey.bin")),
+			#[cfg(all(feature = "rom-jamesbond", not(feature = "no-bundled-roms")))]
+			Jamesbond => Some(include_bytes!("../../roms/jamesbond.bin")),
+			#[cfg(all(feature = "rom-journey-escape", not(feature = "no-bundled-roms")))]
+			JourneyEscape => Some(include_bytes!("../../roms/journey_escape.bin")),
+			#[cfg(all(feature = "rom-joust", not(feature = "no-bundled-roms")))]
+			Joust => Some(include_bytes!("../../roms/joust.bin")),
+			#[cfg(all(feature = "rom-kaboom", not(feature = "no-bundled-roms")))]
+			Kaboom => Some(include_bytes!("../../roms/kaboom.bin")),
+			#[cfg(all(feature = "rom-kangaroo", not(feature = "no-bundled-roms")))]
+			Kangaroo => Some(include_bytes!("../../roms/kangaroo.bin")),
+			#[cfg(all(feature = "rom-keystone-kapers", not(feature = "no-bundled-roms")))]
+			KeystoneKapers => Some(include_bytes!("../../roms/keystone_kapers.bin")),
+			#[cfg(all(feature = "rom-king-kong", not(feature = "no-bundled-roms")))]
+			KingKong => Some(include_bytes!("../../roms/king_kong.bin")),
+			#[cfg(all(feature = "rom-klax", not(feature = "no-bundled-roms")))]
+			Klax => Some(include_bytes!("../../roms/klax.bin")),
+			#[cfg(all(feature = "rom-koolaid", not(feature = "no-bundled-roms")))]
+			Koolaid => Some(include_bytes!("../../roms/koolaid.bin")),
+			#[cfg(all(feature = "rom-krull", not(feature = "no-bundled-roms")))]
+			Krull => Some(include_bytes!("../../roms/krull.bin")),
+			#[cfg(all(feature = "rom-kung-fu-master", not(feature = "no-bundled-roms")))]
+			KungFuMaster => Some(include_bytes!("../../roms/kung_fu_master.bin")),
+			#[cfg(all(feature = "rom-laser-gates", not(feature = "no-bundled-roms")))]
+			LaserGates => Some(include_bytes!("../../roms/laser_gates.bin")),
+			#[cfg(all(feature = "rom-lost-luggage", not(feature = "no-bundled-roms")))]
+			LostLuggage => Some(include_bytes!("../../roms/lost_luggage.bin")),
+			#[cfg(all(feature = "rom-mario-bros", not(feature = "no-bundled-roms")))]
+			MarioBros => Some(include_bytes!("../../roms/mario_bros.bin")),
+			#[cfg(all(feature = "rom-maze-craze", not(feature = "no-bundled-roms")))]
+			MazeCraze => Some(include_bytes!("../../roms/maze_craze.bin")),
+			#[cfg(all(feature = "rom-miniature-golf", not(feature = "no-bundled-roms")))]
+			MiniatureGolf => Some(include_bytes!("../../roms/miniature_golf.bin")),
+			#[cfg(all(feature = "rom-montezuma-revenge", not(feature = "no-bundled-roms")))]
+			MontezumaRevenge => Some(include_bytes!("../../roms/montezuma_revenge.bin")),
+			#[cfg(all(feature = "rom-mr-do", not(feature = "no-bundled-roms")))]
+			MrDo => Some(include_bytes!("../../roms/mr_do.bin")),
+			#[cfg(all(feature = "rom-ms-pacman", not(feature = "no-bundled-roms")))]
+			MsPacman => Some(include_bytes!("../../roms/ms_pacman.bin")),
+			#[cfg(all(feature = "rom-name-this-game", not(feature = "no-bundled-roms")))]
+			NameThisGame => Some(include_bytes!("../../roms/name_this_game.bin")),
+			#[cfg(all(feature = "rom-othello", not(feature = "no-bundled-roms")))]
+			Othello => Some(include_bytes!("../../roms/othello.bin")),
+			#[cfg(all(feature = "rom-pacman", not(feature = "no-bundled-roms")))]
+			Pacman => Some(include_bytes!("../../roms/pacman.bin")),
+			#[cfg(all(feature = "rom-phoenix", not(feature = "no-bundled-roms")))]
+			Phoenix => Some(include_bytes!("../../roms/phoenix.bin")),
+			#[cfg(all(feature = "rom-pitfall", not(feature = "no-bundled-roms")))]
+			Pitfall => Some(include_bytes!("../../roms/pitfall.bin")),
+			#[cfg(all(feature = "rom-pitfall2", not(feature = "no-bundled-roms")))]
+			Pitfall2 => Some(include_bytes!("../../roms/pitfall2.bin")),
+			#[cfg(all(feature = "rom-pong", not(feature = "no-bundled-roms")))]
+			Pong => Some(include_bytes!("../../roms/pong.bin")),
+			#[cfg(all(feature = "rom-pooyan", not(feature = "no-bundled-roms")))]
+			Pooyan => Some(include_bytes!("../../roms/pooyan.bin")),
+			#[cfg(all(feature = "rom-private-eye", not(feature = "no-bundled-roms")))]
+			PrivateEye => Some(include_bytes!("../../roms/private_eye.bin")),
+			#[cfg(all(feature = "rom-qbert", not(feature = "no-bundled-roms")))]
+			Qbert => Some(include_bytes!("../../roms/qbert.bin")),
+			#[cfg(all(feature = "rom-riverraid", not(feature = "no-bundled-roms")))]
+			Riverraid => Some(include_bytes!("../../roms/riverraid.bin")),
+			#[cfg(all(feature = "rom-road-runner", not(feature = "no-bundled-roms")))]
+			RoadRunner => Some(include_bytes!("../../roms/road_runner.bin")),
+			#[cfg(all(feature = "rom-robotank", not(feature = "no-bundled-roms")))]
+			Robotank => Some(include_bytes!("../../roms/robotank.bin")),
+			#[cfg(all(feature = "rom-seaquest", not(feature = "no-bundled-roms")))]
+			Seaquest => Some(include_bytes!("../../roms/seaquest.bin")),
+			#[cfg(all(feature = "rom-sir-lancelot", not(feature = "no-bundled-roms")))]
+			SirLancelot => Some(include_bytes!("../../roms/sir_lancelot.bin")),
+			#[cfg(all(feature = "rom-skiing", not(feature = "no-bundled-roms")))]
+			Skiing => Some(include_bytes!("../../roms/skiing.bin")),
+			#[cfg(all(feature = "rom-solaris", not(feature = "no-bundled-roms")))]
+			Solaris => Some(include_bytes!("../../roms/solaris.bin")),
+			#[cfg(all(feature = "rom-space-invaders", not(feature = "no-bundled-roms")))]
+			SpaceInvaders => Some(include_bytes!("../../roms/space_invaders.bin")),
+			#[cfg(all(feature = "rom-space-war", not(feature = "no-bundled-roms")))]
+			SpaceWar => Some(include_bytes!("../../roms/space_war.bin")),
+			#[cfg(all(feature = "rom-star-gunner", not(feature = "no-bundled-roms")))]
+			StarGunner => Some(include_bytes!("../../roms/star_gunner.bin")),
+			#[cfg(all(feature = "rom-superman", not(feature = "no-bundled-roms")))]
+			Superman => Some(include_bytes!("../../roms/superman.bin")),
+			#[cfg(all(feature = "rom-surround", not(feature = "no-bundled-roms")))]
+			Surround => Some(include_bytes!("../../roms/surround.bin")),
+			#[cfg(all(feature = "rom-tennis", not(feature = "no-bundled-roms")))]
+			Tennis => Some(include_bytes!("../../roms/tennis.bin")),
+			#[cfg(all(feature = "rom-tetris", not(feature = "no-bundled-roms")))]
+			Tetris => Some(include_bytes!("../../roms/tetris.bin")),
+			#[cfg(all(feature = "rom-tic-tac-toe-3d", not(feature = "no-bundled-roms")))]
+			TicTacToe3d => Some(include_bytes!("../../roms/tic_tac_toe_3d.bin")),
+			#[cfg(all(feature = "rom-time-pilot", not(feature = "no-bundled-roms")))]
+			TimePilot => Some(include_bytes!("../../roms/time_pilot.bin")),
+			#[cfg(all(feature = "rom-trondead", not(feature = "no-bundled-roms")))]
+			Trondead => Some(include_bytes!("../../roms/trondead.bin")),
+			#[cfg(all(feature = "rom-turmoil", not(feature = "no-bundled-roms")))]
+			Turmoil => Some(include_bytes!("../../roms/turmoil.bin")),
+			#[cfg(all(feature = "rom-tutankham", not(feature = "no-bundled-roms")))]
+			Tutankham => Some(include_bytes!("../../roms/tutankham.bin")),
+			#[cfg(all(feature = "rom-up-n-down", not(feature = "no-bundled-roms")))]
+			UpNDown => Some(include_bytes!("../../roms/up_n_down.bin")),
+			#[cfg(all(feature = "rom-venture", not(feature = "no-bundled-roms")))]
+			Venture => Some(include_bytes!("../../roms/venture.bin")),
+			#[cfg(all(feature = "rom-video-checkers", not(feature = "no-bundled-roms")))]
+			VideoCheckers => Some(include_bytes!("../../roms/video_checkers.bin")),
+			#[cfg(all(feature = "rom-video-chess", not(feature = "no-bundled-roms")))]
+			VideoChess => Some(include_bytes!("../../roms/video_chess.bin")),
+			#[cfg(all(feature = "rom-video-cube", not(feature = "no-bundled-roms")))]
+			VideoCube => Some(include_bytes!("../../roms/video_cube.bin")),
+			#[cfg(all(feature = "rom-video-pinball", not(feature = "no-bundled-roms")))]
+			VideoPinball => Some(include_bytes!("../../roms/video_pinball.bin")),
+			#[cfg(all(feature = "rom-warlords", not(feature = "no-bundled-roms")))]
+			Warlords => Some(include_bytes!("../../roms/warlords.bin")),
+			#[cfg(all(feature = "rom-wizard-of-wor", not(feature = "no-bundled-roms")))]
+			WizardOfWor => Some(include_bytes!("../../roms/wizard_of_wor.bin")),
+			#[cfg(all(feature = "rom-word-zapper", not(feature = "no-bundled-roms")))]
+			WordZapper => Some(include_bytes!("../../roms/word_zapper.bin")),
+			#[cfg(all(feature = "rom-yars-revenge", not(feature = "no-bundled-roms")))]
+			YarsRevenge => Some(include_bytes!("../../roms/yars_revenge.bin")),
+			#[cfg(all(feature = "rom-zaxxon", not(feature = "no-bundled-roms")))]
+			Zaxxon => Some(include_bytes!("../../roms/zaxxon.bin")),
+			#[allow(unreachable_patterns)]
+			_ => None,
+		}
+	}
+}
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::{AleError, Result};
+
+/// Environment variable that overrides the directory ROMs are cached in.
+pub const ROM_CACHE_DIR_ENV: &str = "ALE_ROM_CACHE_DIR";
+
+/// Paths of the ROMs that have been written to or found in the cache by this process, whose content is known to match
+/// the checksum in their path.
+static WRITTEN: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// Returns the directory ROMs are cached in.
+///
+/// The ALE can only load ROMs from files, so in-memory ROMs are written to a cache directory that persists between
+/// processes: `$ALE_ROM_CACHE_DIR` if set, otherwise `ale-rs/roms` in the user's cache directory (`$XDG_CACHE_HOME`
+/// or `~/.cache`), falling back to a per-user directory in the system's temporary directory.
+pub(crate) fn cache_dir() -> PathBuf {
+	if let Some(dir) = std::env::var_os(ROM_CACHE_DIR_ENV) {
+		return dir.into();
+	}
+	let cache_home = std::env::var_os("XDG_CACHE_HOME")
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")));
+	match cache_home {
+		Some(cache_home) => cache_home.join("ale-rs").join("roms"),
+		None => {
+			let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default();
+			std::env::temp_dir().join(format!("ale-rs-roms-{}", user))
+		}
+	}
+}
+
+/// Returns the path of a file named `name` containing `data`, writing it if it is not in the cache yet.
+///
+/// Files are stored at `<cache_dir>/<md5>/<name>`, so every distinct ROM is only written once per user. A cached file
+/// is only reused if its content still matches, and files are written atomically so that concurrent processes never
+/// load a partially written ROM. Files that are removed while the process runs, e.g. by cleaning the cache, are
+/// written again.
+///
+/// The ALE uses the file name to pick the settings for the game, so `name` must be a plain file name such as
+/// `"breakout.bin"`.
+pub(crate) fn rom_file(name: &str, data: &[u8]) -> Result<PathBuf> {
+	rom_file_in(&cache_dir(), name, data)
+}
+
+/// Same as [`rom_file`], but caches the file in `cache_dir`.
+fn rom_file_in(cache_dir: &Path, name: &str, data: &[u8]) -> Result<PathBuf> {
+	if name.is_empty() || Path::new(name).file_name().and_then(|n| n.to_str()) != Some(name) {
+		return Err(AleError::RomLoad { path: name.into(), reason: "ROM name must be a plain file name".into() });
+	}
+	let md5 = md5::compute(data);
+	let dir = cache_dir.join(format!("{:x}", md5));
+	let path = dir.join(name);
+
+	let mut written = WRITTEN.lock().unwrap_or_else(|e| e.into_inner());
+	let written = written.get_or_insert_with(HashSet::new);
+	let is_file = |path: &Path| std::fs::metadata(path).map(|metadata| metadata.is_file()).unwrap_or(false);
+	if written.contains(&path) && is_file(&path) {
+		return Ok(path);
+	}
+
+	let cached = std::fs::read(&path).map(|cached| md5::compute(cached) == md5).unwrap_or(false);
+	if !cached {
+		std::fs::create_dir_all(&dir)?;
+		let tmp = dir.join(format!(".{}.{}.tmp", name, std::process::id()));
+		std::fs::write(&tmp, data)?;
+		std::fs::rename(&tmp, &path)?;
+	}
+	written.insert(path.clone());
+	Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rom_files_are_keyed_by_md5() {
+		let dir = std::env::temp_dir().join(format!("ale-rs-rom-cache-test-{}", std::process::id()));
+		let path = rom_file_in(&dir, "game.bin", b"first").unwrap();
+		assert_eq!(path, dir.join(format!("{:x}", md5::compute(b"first"))).join("game.bin"));
+		assert_eq!(std::fs::read(&path).unwrap(), b"first");
+		assert_eq!(rom_file_in(&dir, "game.bin", b"first").unwrap(), path);
+		let other = rom_file_in(&dir, "game.bin", b"second").unwrap();
+		assert_ne!(other, path);
+		assert_eq!(std::fs::read(&other).unwrap(), b"second");
+		assert!(rom_file_in(&dir, "../game.bin", b"first").is_err());
+
+		// Files removed from the cache while the process runs are written again
+		std::fs::remove_dir_all(&dir).unwrap();
+		assert_eq!(rom_file_in(&dir, "game.bin", b"first").unwrap(), path);
+		assert_eq!(std::fs::read(&path).unwrap(), b"first");
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// A setting of the ALE, along with its value.
+///
+/// Passed to [`Ale::set`](crate::Ale::set), so that the type of the value is checked at compile time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Setting {
+	/// Seed of the ALE's random number generator (`random_seed`).
+	RandomSeed(i32),
+	/// Number of frames each action is repeated for (`frame_skip`).
+	FrameSkip(i32),
+	/// Probability of the previous action being repeated instead of the chosen one (`repeat_action_probability`).
+	RepeatActionProbability(f32),
+	/// Whether the screen is averaged with the previous frame, to remove flickering (`color_averaging`).
+	ColorAveraging(bool),
+	/// Maximum number of frames before the ALE ends the game, or `0` for no limit (`max_num_frames`).
+	MaxNumFrames(i32),
+	/// Maximum number of frames per episode before the ALE ends the game, or `0` for no limit
+	/// (`max_num_frames_per_episode`).
+	MaxNumFramesPerEpisode(i32),
+	/// Whether the game ends when a life is lost (`truncate_on_loss_of_life`).
+	TruncateOnLossOfLife(bool),
+	/// Directory that every frame is saved to as a PNG, or empty to disable (`record_screen_dir`).
+	RecordScreenDir(String),
+	/// File that the sound is recorded to, or empty to disable (`record_sound_filename`).
+	RecordSoundFilename(String),
+	/// Whether sound is enabled (`sound`).
+	Sound(bool),
+	/// Whether the screen is displayed in a window (`display_screen`).
+	DisplayScreen(bool),
+}
+impl Setting {
+	/// Returns the key of this setting.
+	pub fn key(&self) -> SettingKey {
+		use Setting::*;
+		match self {
+			RandomSeed(_) => SettingKey::RandomSeed,
+			FrameSkip(_) => SettingKey::FrameSkip,
+			RepeatActionProbability(_) => SettingKey::RepeatActionProbability,
+			ColorAveraging(_) => SettingKey::ColorAveraging,
+			MaxNumFrames(_) => SettingKey::MaxNumFrames,
+			MaxNumFramesPerEpisode(_) => SettingKey::MaxNumFramesPerEpisode,
+			TruncateOnLossOfLife(_) => SettingKey::TruncateOnLossOfLife,
+			RecordScreenDir(_) => SettingKey::RecordScreenDir,
+			RecordSoundFilename(_) => SettingKey::RecordSoundFilename,
+			Sound(_) => SettingKey::Sound,
+			DisplayScreen(_) => SettingKey::DisplayScreen,
+		}
+	}
+}
+impl fmt::Display for Setting {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use Setting::*;
+		write!(f, "{} = ", self.key().name())?;
+		match self {
+			RandomSeed(v) | FrameSkip(v) | MaxNumFrames(v) | MaxNumFramesPerEpisode(v) => write!(f, "{}", v),
+			RepeatActionProbability(v) => write!(f, "{}", v),
+			ColorAveraging(v) | TruncateOnLossOfLife(v) | Sound(v) | DisplayScreen(v) => write!(f, "{}", v),
+			RecordScreenDir(v) | RecordSoundFilename(v) => write!(f, "{:?}", v),
+		}
+	}
+}
+
+/// Type of the value of a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingType {
+	Int,
+	Float,
+	Bool,
+	String,
+}
+
+/// Key of a known ALE setting, used to read it with [`Ale::get`](crate::Ale::get).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingKey {
+	RandomSeed,
+	FrameSkip,
+	RepeatActionProbability,
+	ColorAveraging,
+	MaxNumFrames,
+	MaxNumFramesPerEpisode,
+	TruncateOnLossOfLife,
+	RecordScreenDir,
+	RecordSoundFilename,
+	Sound,
+	DisplayScreen,
+}
+impl SettingKey {
+	/// Every known setting.
+	pub const ALL: [SettingKey; 11] = [
+		SettingKey::RandomSeed,
+		SettingKey::FrameSkip,
+		SettingKey::RepeatActionProbability,
+		SettingKey::ColorAveraging,
+		SettingKey::MaxNumFrames,
+		SettingKey::MaxNumFramesPerEpisode,
+		SettingKey::TruncateOnLossOfLife,
+		SettingKey::RecordScreenDir,
+		SettingKey::RecordSoundFilename,
+		SettingKey::Sound,
+		SettingKey::DisplayScreen,
+	];
+
+	/// Returns the name that the ALE uses for this setting.
+	pub fn name(&self) -> &'static str {
+		use SettingKey::*;
+		match self {
+			RandomSeed => "random_seed",
+			FrameSkip => "frame_skip",
+			RepeatActionProbability => "repeat_action_probability",
+			ColorAveraging => "color_averaging",
+			MaxNumFrames => "max_num_frames",
+			MaxNumFramesPerEpisode => "max_num_frames_per_episode",
+			TruncateOnLossOfLife => "truncate_on_loss_of_life",
+			RecordScreenDir => "record_screen_dir",
+			RecordSoundFilename => "record_sound_filename",
+			Sound => "sound",
+			DisplayScreen => "display_screen",
+		}
+	}
+
+	/// Returns the type of the setting's value.
+	pub fn setting_type(&self) -> SettingType {
+		use SettingKey::*;
+		match self {
+			RandomSeed | FrameSkip | MaxNumFrames | MaxNumFramesPerEpisode => SettingType::Int,
+			RepeatActionProbability => SettingType::Float,
+			ColorAveraging | TruncateOnLossOfLife | Sound | DisplayScreen => SettingType::Bool,
+			RecordScreenDir | RecordSoundFilename => SettingType::String,
+		}
+	}
+}
+impl fmt::Display for SettingKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.name()) }
+}
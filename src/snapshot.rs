@@ -0,0 +1,36 @@
+use crate::AleState;
+
+/// Snapshot of an [`Ale`](crate::Ale) instance, returned by [`Ale::snapshot`](crate::Ale::snapshot).
+///
+/// Unlike an [`AleState`], this also captures the episode bookkeeping that is kept on the Rust side, i.e. the score
+/// and the number of lives that life loss is detected against, so that stepping after a restore behaves exactly as
+/// stepping from the point the snapshot was taken.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvSnapshot {
+	pub(crate) state: AleState,
+	pub(crate) score: i32,
+	pub(crate) lives: u8,
+}
+impl EnvSnapshot {
+	/// Returns the emulator state of the snapshot.
+	pub fn state(&self) -> &AleState {
+		&self.state
+	}
+
+	/// Returns whether the snapshot includes the system state, i.e. pseudorandomness. See
+	/// [`Ale::system_snapshot`](crate::Ale::system_snapshot).
+	pub fn is_system(&self) -> bool {
+		self.state.is_system()
+	}
+
+	/// Returns the total reward received since the start of the episode, up to the snapshot.
+	pub fn score(&self) -> i32 {
+		self.score
+	}
+
+	/// Returns the number of lives at the time of the snapshot.
+	pub fn lives(&self) -> i32 {
+		self.lives.into()
+	}
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// Element type of a [`Space::Box`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dtype {
+	U8,
+	I32,
+	F32,
+}
+impl Dtype {
+	/// Returns the name of the type as used by NumPy, e.g. `"uint8"`.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Dtype::U8 => "uint8",
+			Dtype::I32 => "int32",
+			Dtype::F32 => "float32",
+		}
+	}
+
+	/// Returns the size of one element in bytes.
+	pub fn size(&self) -> usize {
+		match self {
+			Dtype::U8 => 1,
+			Dtype::I32 | Dtype::F32 => 4,
+		}
+	}
+}
+impl fmt::Display for Dtype {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.name()) }
+}
+
+/// The set of valid actions or observations of an [`Environment`](crate::Environment), mirroring Gymnasium's spaces.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Space {
+	/// The integers `0..n`.
+	Discrete { n: usize },
+	/// Arrays of the given shape and element type, where every element is within `low..=high`.
+	Box { shape: Vec<usize>, dtype: Dtype, low: f64, high: f64 },
+}
+impl Space {
+	/// Creates a box of bytes covering their whole range, i.e. `0..=255`, as used for images and RAM.
+	pub fn bytes(shape: Vec<usize>) -> Space {
+		Space::Box { shape, dtype: Dtype::U8, low: 0.0, high: 255.0 }
+	}
+
+	/// Returns the shape of the elements of the space, which is empty for a discrete space.
+	pub fn shape(&self) -> &[usize] {
+		match self {
+			Space::Discrete { .. } => &[],
+			Space::Box { shape, .. } => shape,
+		}
+	}
+
+	/// Returns the number of scalars in an element of the space, i.e. the product of its shape.
+	pub fn len(&self) -> usize {
+		self.shape().iter().product()
+	}
+
+	/// Returns whether an element of the space contains no scalars, i.e. one of its dimensions is zero.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns whether `action` is a valid element of a discrete space. Always returns `false` for a box.
+	pub fn contains(&self, action: usize) -> bool {
+		match self {
+			Space::Discrete { n } => action < *n,
+			Space::Box { .. } => false,
+		}
+	}
+}
+impl fmt::Display for Space {
+	/// Displays the space like Gymnasium does, e.g. `Discrete(4)` or `Box(0, 255, (210, 160, 3), uint8)`.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Space::Discrete { n } => write!(f, "Discrete({})", n),
+			Space::Box { shape, dtype, low, high } => {
+				let shape: Vec<_> = shape.iter().map(usize::to_string).collect();
+				let trailing_comma = if shape.len() == 1 { "," } else { "" };
+				write!(f, "Box({}, {}, ({}{}), {})", low, high, shape.join(", "), trailing_comma, dtype)
+			}
+		}
+	}
+}
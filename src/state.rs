@@ -0,0 +1,310 @@
+use std::convert::TryInto;
+use std::fmt;
+use std::os::raw::c_int;
+
+use crate::atari::to_size;
+use crate::error::check_buffer;
+use crate::{AleError, Result};
+
+/// Magic bytes at the start of every encoded state.
+const MAGIC: [u8; 4] = *b"ALEs";
+/// Length of the header that precedes the ALE's own encoding of a state:
+///
+/// | Bytes | Content                                                                  |
+/// |-------|--------------------------------------------------------------------------|
+/// | 4     | Magic bytes `ALEs`                                                       |
+/// | 2     | Format version, little endian                                            |
+/// | 1     | Flags: `1` if it is a system state, `2` if the ROM checksum is present   |
+/// | 16    | MD5 checksum of the ROM the state was cloned from, or zeros              |
+/// | 16    | MD5 checksum of the ALE's encoding                                       |
+/// | 4     | Length of the ALE's encoding, little endian                              |
+const HEADER_LEN: usize = 4 + 2 + 1 + 16 + 16 + 4;
+const FLAG_SYSTEM: u8 = 1;
+const FLAG_ROM: u8 = 2;
+
+/// State of the ALE
+///
+/// Used mainly by [`Ale::clone_state`] & [`Ale::restore_state`] to save the emulator's state, and restore it at a later point.
+///
+/// A state is independent of the emulator it was cloned from, so it can be sent to other threads and restored into any
+/// emulator that has the same ROM loaded. With the `serde` feature it can be serialized as its encoded bytes.
+///
+/// [`Ale::clone_state`]: crate::Ale::clone_state
+/// [`Ale::restore_state`]: crate::Ale::restore_state
+pub struct AleState {
+	pub(crate) ptr: *mut ale_sys::ALEState,
+	pub(crate) rom_md5: Option<[u8; 16]>,
+	pub(crate) system: bool,
+}
+impl AleState {
+	/// Version of the encoding written by [`AleState::encode_state`]. Only states of this version can be decoded.
+	pub const FORMAT_VERSION: u16 = 1;
+
+	/// Length of the header written by [`AleState::encode_state`] before the ALE's own encoding. Only meant for fuzzing.
+	#[doc(hidden)]
+	pub const HEADER_LEN: usize = HEADER_LEN;
+
+	/// Returns the MD5 checksum of the ROM that was loaded when the state was cloned, as a lowercase hex string.
+	///
+	/// A state can only be restored into an emulator that has the same ROM loaded.
+	pub fn rom_md5(&self) -> Option<String> {
+		self.rom_md5.map(|md5| format!("{:x}", md5::Digest(md5)))
+	}
+
+	/// Returns whether this is a system state, i.e. it includes pseudorandomness. See
+	/// [`Ale::clone_system_state`](crate::Ale::clone_system_state).
+	pub fn is_system(&self) -> bool {
+		self.system
+	}
+
+	/// Encodes the state as a raw bytestream.
+	///
+	/// The encoding starts with a header holding the format version, the ROM's checksum and a checksum of the rest of
+	/// the data, which [`AleState::decode_state`] verifies.
+	///
+	/// Returns [`AleError::BufferTooSmall`] if the length of `buf` is not large enough. Use
+	/// [`AleState::encode_state_len`] to get the needed length.
+	pub fn encode_state(&self, buf: &mut [u8]) -> Result<()> {
+		let payload_len = self.payload_len()?;
+		check_buffer(buf, HEADER_LEN + payload_len)?;
+		let (header, payload) = buf.split_at_mut(HEADER_LEN);
+		let payload = &mut payload[..payload_len];
+		unsafe {
+			ale_sys::encodeState(self.ptr, payload.as_mut_ptr() as *mut _, payload_len as c_int);
+		}
+		write_header(header, self.rom_md5, self.system, payload);
+		Ok(())
+	}
+
+	/// Encodes `payload` as if it was the ALE's encoding of a state, behind a valid header, so that it passes the checks
+	/// of [`AleState::decode_state`]. Only meant for fuzzing the ALE's own decoding, which does not validate its input.
+	#[doc(hidden)]
+	pub fn encode_payload(rom_md5: Option<[u8; 16]>, system: bool, payload: &[u8]) -> Vec<u8> {
+		let mut buf = vec![0; HEADER_LEN + payload.len()];
+		let (header, rest) = buf.split_at_mut(HEADER_LEN);
+		rest.copy_from_slice(payload);
+		write_header(header, rom_md5, system, payload);
+		buf
+	}
+
+	/// Returns the length of the buffer needed to encode the state.
+	///
+	/// Returns [`AleError::InvalidSize`] if the C API returns a negative size.
+	pub fn encode_state_len(&self) -> Result<usize> {
+		Ok(HEADER_LEN + self.payload_len()?)
+	}
+
+	fn payload_len(&self) -> Result<usize> {
+		to_size(unsafe { ale_sys::encodeStateLen(self.ptr) })
+	}
+
+	/// Decode state from a raw bytestream encoded by [`AleState::encode_state`].
+	///
+	/// Returns [`AleError::StateDecode`] if the data is not an encoded state, was encoded with a different format
+	/// version, or does not match its checksum, in which case it is never passed to the ALE. The checksum protects
+	/// against truncated or corrupted data, not against data crafted to pass it.
+	pub fn decode_state(serialized: &[u8]) -> Result<AleState> {
+		let decode_error = AleError::StateDecode;
+		if serialized.len() < HEADER_LEN {
+			return Err(decode_error(format!("length {} is too short for the header", serialized.len())));
+		}
+		let (header, payload) = serialized.split_at(HEADER_LEN);
+		if header[0..4] != MAGIC {
+			return Err(decode_error("data is not an encoded ALE state".into()));
+		}
+		let version = u16::from_le_bytes(header[4..6].try_into().unwrap());
+		if version != AleState::FORMAT_VERSION {
+			return Err(decode_error(format!(
+				"unsupported format version {}, expected {}",
+				version,
+				AleState::FORMAT_VERSION
+			)));
+		}
+		let flags = header[6];
+		if flags & !(FLAG_SYSTEM | FLAG_ROM) != 0 {
+			return Err(decode_error(format!("unknown flags {:#04x}", flags)));
+		}
+		let payload_len = u32::from_le_bytes(header[39..43].try_into().unwrap());
+		if payload_len as usize != payload.len() {
+			return Err(decode_error(format!("expected {} bytes of state, got {}", payload_len, payload.len())));
+		}
+		if header[23..39] != md5::compute(payload).0 {
+			return Err(decode_error("checksum mismatch".into()));
+		}
+		let len: c_int =
+			payload.len().try_into().map_err(|_| decode_error(format!("length {} is too long", payload.len())))?;
+
+		let ptr = unsafe { ale_sys::decodeState(payload.as_ptr() as *const _, len) };
+		if ptr.is_null() {
+			return Err(decode_error("the ALE returned a null state".into()));
+		}
+		Ok(AleState {
+			ptr,
+			rom_md5: if flags & FLAG_ROM != 0 { Some(header[7..23].try_into().unwrap()) } else { None },
+			system: flags & FLAG_SYSTEM != 0,
+		})
+	}
+
+	/// Encodes the state into a new buffer of the needed length.
+	///
+	/// # Panics
+	/// Panics if the C API returns a negative length for the encoded state.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut buf = vec![0; self.encode_state_len().expect("invalid encoded state length")];
+		self.encode_state(&mut buf).expect("buffer is large enough");
+		buf
+	}
+
+	/// Decodes a state encoded by [`AleState::to_bytes`]. This is the same as [`AleState::decode_state`].
+	pub fn from_bytes(bytes: &[u8]) -> Result<AleState> {
+		AleState::decode_state(bytes)
+	}
+}
+/// Writes the header of an encoded state into the first [`HEADER_LEN`] bytes of `header`.
+fn write_header(header: &mut [u8], rom_md5: Option<[u8; 16]>, system: bool, payload: &[u8]) {
+	let mut flags = 0;
+	if system {
+		flags |= FLAG_SYSTEM;
+	}
+	if rom_md5.is_some() {
+		flags |= FLAG_ROM;
+	}
+	header[0..4].copy_from_slice(&MAGIC);
+	header[4..6].copy_from_slice(&AleState::FORMAT_VERSION.to_le_bytes());
+	header[6] = flags;
+	header[7..23].copy_from_slice(&rom_md5.unwrap_or_default());
+	header[23..39].copy_from_slice(&md5::compute(payload).0);
+	header[39..43].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+}
+
+// The ALE only accesses a state through the calls made by its owner, and the state holds no reference to the emulator
+// it was cloned from.
+unsafe impl Send for AleState {}
+impl Clone for AleState {
+	/// Copies the state by encoding and decoding it, as the C API has no way to copy a state directly.
+	fn clone(&self) -> AleState {
+		AleState::from_bytes(&self.to_bytes()).expect("failed to decode a copy of a valid state")
+	}
+}
+impl fmt::Debug for AleState {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("AleState")
+			.field("rom_md5", &self.rom_md5())
+			.field("system", &self.system)
+			.finish_non_exhaustive()
+	}
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for AleState {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		serializer.serialize_bytes(&self.to_bytes())
+	}
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AleState {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<AleState, D::Error> {
+		struct BytesVisitor;
+		impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+			type Value = Vec<u8>;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("an encoded ALE state") }
+
+			fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> std::result::Result<Vec<u8>, E> { Ok(v.to_vec()) }
+
+			fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> std::result::Result<Vec<u8>, E> { Ok(v) }
+
+			fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Vec<u8>, A::Error> {
+				let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+				while let Some(byte) = seq.next_element()? {
+					bytes.push(byte);
+				}
+				Ok(bytes)
+			}
+		}
+		let bytes = deserializer.deserialize_byte_buf(BytesVisitor)?;
+		AleState::from_bytes(&bytes).map_err(serde::de::Error::custom)
+	}
+}
+impl Drop for AleState {
+	fn drop(&mut self) {
+		unsafe {
+			let ptr = self.ptr;
+			self.ptr = std::ptr::null_mut();
+			ale_sys::deleteState(ptr);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn encoded(rom_md5: [u8; 16], payload: &[u8]) -> Vec<u8> { AleState::encode_payload(Some(rom_md5), false, payload) }
+
+	fn decode_error(serialized: &[u8]) -> String {
+		match AleState::decode_state(serialized) {
+			Err(AleError::StateDecode(reason)) => reason,
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("malformed state was decoded"),
+		}
+	}
+
+	#[test]
+	fn encodes_the_header() {
+		let data = AleState::encode_payload(Some([1; 16]), true, b"payload");
+		assert_eq!(&data[..6], b"ALEs\x01\x00");
+		assert_eq!(data[6], FLAG_SYSTEM | FLAG_ROM);
+		assert_eq!(data[7..23], [1; 16]);
+		assert_eq!(data[23..39], md5::compute(b"payload").0);
+		assert_eq!(data[39..43], 7u32.to_le_bytes());
+		assert_eq!(&data[HEADER_LEN..], b"payload");
+		assert_eq!(AleState::encode_payload(None, false, b"")[6], 0);
+	}
+
+	#[test]
+	fn rejects_truncated_header() {
+		let valid = encoded([1; 16], b"payload");
+		assert!(decode_error(&[]).contains("too short"));
+		assert!(decode_error(&valid[..HEADER_LEN - 1]).contains("too short"));
+	}
+
+	#[test]
+	fn rejects_bad_magic() {
+		let mut data = encoded([1; 16], b"payload");
+		data[0] = b'X';
+		assert!(decode_error(&data).contains("not an encoded ALE state"));
+	}
+
+	#[test]
+	fn rejects_other_versions() {
+		let mut data = encoded([1; 16], b"payload");
+		data[4..6].copy_from_slice(&(AleState::FORMAT_VERSION + 1).to_le_bytes());
+		assert!(decode_error(&data).contains("unsupported format version"));
+	}
+
+	#[test]
+	fn rejects_unknown_flags() {
+		let mut data = encoded([1; 16], b"payload");
+		data[6] |= 0x80;
+		assert!(decode_error(&data).contains("unknown flags"));
+	}
+
+	#[test]
+	fn rejects_truncated_payload() {
+		let data = encoded([1; 16], b"payload");
+		assert!(decode_error(&data[..data.len() - 1]).contains("expected 7 bytes of state, got 6"));
+		let mut extended = data;
+		extended.push(0);
+		assert!(decode_error(&extended).contains("expected 7 bytes of state, got 8"));
+	}
+
+	#[test]
+	fn rejects_bad_payload_checksum() {
+		let mut data = encoded([1; 16], b"payload");
+		*data.last_mut().unwrap() ^= 1;
+		assert!(decode_error(&data).contains("checksum mismatch"));
+		let mut data = encoded([1; 16], b"payload");
+		data[23] ^= 1;
+		assert!(decode_error(&data).contains("checksum mismatch"));
+	}
+}
@@ -0,0 +1,341 @@
+//! Reading and writing of `.alestate` files.
+
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::{AleError, Result};
+
+const MAGIC: [u8; 8] = *b"ALESTATE";
+const HEADER_LEN: usize = 8 + 2 + 1 + 16 + 1 + 4 * 6 + 8 + 8;
+const FLAG_SYSTEM: u8 = 1;
+const FLAG_COMPRESSED: u8 = 2;
+const FLAG_MODE: u8 = 1;
+const FLAG_DIFFICULTY: u8 = 2;
+
+/// Header of a `.alestate` file, describing the emulator the state was saved from.
+///
+/// State files are written by [`Ale::save_state_file`](crate::Ale::save_state_file) and read by
+/// [`Ale::load_state_file`](crate::Ale::load_state_file), which returns the header. The header can also be read
+/// without restoring the state with [`StateFileHeader::read`].
+///
+/// # Format
+/// A state file starts with a fixed size header, followed by an encoded [`AleState`](crate::AleState). All integers are
+/// little endian.
+///
+/// | Bytes | Content                                                                                |
+/// |-------|----------------------------------------------------------------------------------------|
+/// | 8     | Magic bytes `ALESTATE`                                                                 |
+/// | 2     | File format version, currently `1`                                                     |
+/// | 1     | Flags: `1` if it is a system state, `2` if the state is deflate compressed             |
+/// | 16    | MD5 checksum of the ROM                                                                |
+/// | 1     | Flags: `1` if a mode was set, `2` if a difficulty was set                              |
+/// | 4     | Mode, or `0` if the default mode is used                                               |
+/// | 4     | Difficulty, or `0` if the default difficulty is used                                   |
+/// | 4     | Frame number since the ROM was loaded                                                  |
+/// | 4     | Frame number since the start of the episode                                            |
+/// | 4     | Score of the episode                                                                   |
+/// | 4     | Number of lives                                                                        |
+/// | 8     | Length of the state that follows, as stored                                            |
+/// | 8     | Length of the state once decompressed, which is the same if it is not compressed       |
+///
+/// The state itself is the output of [`AleState::to_bytes`](crate::AleState::to_bytes), compressed with deflate if the
+/// compression flag is set. It is never decompressed beyond its recorded length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateFileHeader {
+	/// Version of the file format.
+	pub version: u16,
+	/// MD5 checksum of the ROM that was loaded, as a lowercase hex string.
+	pub rom_md5: String,
+	/// The mode set with [`Ale::set_mode`](crate::Ale::set_mode), or `None` if the default mode was used.
+	pub mode: Option<i32>,
+	/// The difficulty set with [`Ale::set_difficulty`](crate::Ale::set_difficulty), or `None` if the default
+	/// difficulty was used.
+	pub difficulty: Option<i32>,
+	/// Frame number since the ROM was loaded.
+	pub frame_number: i32,
+	/// Frame number since the start of the episode.
+	pub episode_frame_number: i32,
+	/// Total reward received since the start of the episode.
+	pub score: i32,
+	/// Remaining number of lives.
+	pub lives: i32,
+	/// Whether the state includes pseudorandomness, see [`Ale::clone_system_state`](crate::Ale::clone_system_state).
+	pub system: bool,
+	/// Whether the state is deflate compressed.
+	pub compressed: bool,
+}
+impl StateFileHeader {
+	/// Version of the file format written by [`Ale::save_state_file`](crate::Ale::save_state_file). Only files of this
+	/// version can be read.
+	pub const VERSION: u16 = 1;
+
+	/// Reads the header of a state file, without reading the state itself.
+	pub fn read(path: impl AsRef<Path>) -> Result<StateFileHeader> {
+		let mut file = File::open(path)?;
+		StateFileHeader::read_from(&mut file).map(|(header, _)| header)
+	}
+
+	/// Reads the header, along with the stored and decompressed lengths of the state that follows it.
+	fn read_from(reader: &mut impl Read) -> Result<(StateFileHeader, StateLen)> {
+		let mut buf = [0; HEADER_LEN];
+		reader.read_exact(&mut buf).map_err(|e| AleError::StateDecode(format!("failed to read header: {}", e)))?;
+		if buf[0..8] != MAGIC {
+			return Err(AleError::StateDecode("not an ALE state file".into()));
+		}
+		let version = u16::from_le_bytes(buf[8..10].try_into().unwrap());
+		if version != StateFileHeader::VERSION {
+			return Err(AleError::StateDecode(format!(
+				"unsupported state file version {}, expected {}",
+				version,
+				StateFileHeader::VERSION
+			)));
+		}
+		let (flags, settings) = (buf[10], buf[27]);
+		if flags & !(FLAG_SYSTEM | FLAG_COMPRESSED) != 0 || settings & !(FLAG_MODE | FLAG_DIFFICULTY) != 0 {
+			return Err(AleError::StateDecode(format!("unknown flags {:#04x} {:#04x}", flags, settings)));
+		}
+		let int_at = |i: usize| i32::from_le_bytes(buf[i..i + 4].try_into().unwrap());
+		let header = StateFileHeader {
+			version,
+			rom_md5: format!("{:x}", md5::Digest(buf[11..27].try_into().unwrap())),
+			mode: if settings & FLAG_MODE != 0 { Some(int_at(28)) } else { None },
+			difficulty: if settings & FLAG_DIFFICULTY != 0 { Some(int_at(32)) } else { None },
+			frame_number: int_at(36),
+			episode_frame_number: int_at(40),
+			score: int_at(44),
+			lives: int_at(48),
+			system: flags & FLAG_SYSTEM != 0,
+			compressed: flags & FLAG_COMPRESSED != 0,
+		};
+		let len = StateLen {
+			stored: u64::from_le_bytes(buf[52..60].try_into().unwrap()),
+			decompressed: u64::from_le_bytes(buf[60..68].try_into().unwrap()),
+		};
+		if !header.compressed && len.stored != len.decompressed {
+			return Err(AleError::StateDecode(format!(
+				"uncompressed state has a stored length of {} but a decompressed length of {}",
+				len.stored, len.decompressed
+			)));
+		}
+		Ok((header, len))
+	}
+}
+
+/// Lengths of the state in a state file.
+struct StateLen {
+	stored: u64,
+	decompressed: u64,
+}
+
+/// Options for [`Ale::save_state_file`](crate::Ale::save_state_file).
+///
+/// By default the state does not include pseudorandomness, and is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateFileOptions {
+	pub(crate) system: bool,
+	pub(crate) compress: bool,
+}
+impl StateFileOptions {
+	/// Creates the default options.
+	pub fn new() -> StateFileOptions {
+		StateFileOptions { system: false, compress: true }
+	}
+
+	/// Sets whether the system state, which includes pseudorandomness, is saved instead of the environment state.
+	pub fn system(mut self, system: bool) -> StateFileOptions {
+		self.system = system;
+		self
+	}
+
+	/// Sets whether the state is deflate compressed.
+	pub fn compress(mut self, compress: bool) -> StateFileOptions {
+		self.compress = compress;
+		self
+	}
+}
+impl Default for StateFileOptions {
+	fn default() -> StateFileOptions { StateFileOptions::new() }
+}
+
+/// Writes a state file, with the state compressed if `header.compressed` is set. The version of `header` is ignored in
+/// favour of the current version.
+pub(crate) fn write(path: &Path, header: &StateFileHeader, rom_md5: [u8; 16], state: &[u8]) -> Result<()> {
+	let mut file = BufWriter::new(File::create(path)?);
+	write_to(&mut file, header, rom_md5, state)?;
+	file.flush()?;
+	Ok(())
+}
+
+fn write_to(writer: &mut impl Write, header: &StateFileHeader, rom_md5: [u8; 16], state: &[u8]) -> Result<()> {
+	let decompressed_len = state.len();
+	let state = if header.compressed {
+		let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+		encoder.write_all(state)?;
+		encoder.finish()?
+	} else {
+		state.to_vec()
+	};
+
+	let mut buf = [0; HEADER_LEN];
+	buf[0..8].copy_from_slice(&MAGIC);
+	buf[8..10].copy_from_slice(&StateFileHeader::VERSION.to_le_bytes());
+	buf[10] = if header.system { FLAG_SYSTEM } else { 0 } | if header.compressed { FLAG_COMPRESSED } else { 0 };
+	buf[11..27].copy_from_slice(&rom_md5);
+	buf[27] = if header.mode.is_some() { FLAG_MODE } else { 0 }
+		| if header.difficulty.is_some() { FLAG_DIFFICULTY } else { 0 };
+	let ints = [
+		header.mode.unwrap_or(0),
+		header.difficulty.unwrap_or(0),
+		header.frame_number,
+		header.episode_frame_number,
+		header.score,
+		header.lives,
+	];
+	for (i, value) in ints.iter().enumerate() {
+		buf[28 + i * 4..32 + i * 4].copy_from_slice(&value.to_le_bytes());
+	}
+	buf[52..60].copy_from_slice(&(state.len() as u64).to_le_bytes());
+	buf[60..68].copy_from_slice(&(decompressed_len as u64).to_le_bytes());
+
+	writer.write_all(&buf)?;
+	writer.write_all(&state)?;
+	Ok(())
+}
+
+/// Reads a state file, returning its header and the decompressed state.
+pub(crate) fn read(path: &Path) -> Result<(StateFileHeader, Vec<u8>)> {
+	read_from(&mut BufReader::new(File::open(path)?))
+}
+
+fn read_from(reader: &mut impl Read) -> Result<(StateFileHeader, Vec<u8>)> {
+	let (header, len) = StateFileHeader::read_from(reader)?;
+	// Reading one byte more than expected detects trailing data, without reading all of it
+	let mut data = vec![];
+	reader.take(len.stored.saturating_add(1)).read_to_end(&mut data)?;
+	check_len("state", len.stored, &data)?;
+	if !header.compressed {
+		return Ok((header, data));
+	}
+	// A crafted state could decompress to much more than its recorded length, so never decompress beyond it
+	let mut state = vec![];
+	DeflateDecoder::new(&data[..])
+		.take(len.decompressed.saturating_add(1))
+		.read_to_end(&mut state)
+		.map_err(|e| AleError::StateDecode(format!("failed to decompress state: {}", e)))?;
+	check_len("decompressed state", len.decompressed, &state)?;
+	Ok((header, state))
+}
+
+/// Checks that `data`, which was read up to one byte past the expected length, has the expected length.
+fn check_len(what: &str, expected: u64, data: &[u8]) -> Result<()> {
+	let actual = data.len() as u64;
+	if actual == expected {
+		return Ok(());
+	}
+	let actual = if actual > expected { "more".to_owned() } else { actual.to_string() };
+	Err(AleError::StateDecode(format!("expected {} bytes of {}, got {}", expected, what, actual)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ROM_MD5: [u8; 16] = [0x12; 16];
+
+	fn header(compressed: bool, mode: Option<i32>, difficulty: Option<i32>) -> StateFileHeader {
+		StateFileHeader {
+			version: StateFileHeader::VERSION,
+			rom_md5: "12".repeat(16),
+			mode,
+			difficulty,
+			frame_number: 1234,
+			episode_frame_number: 56,
+			score: -7,
+			lives: 3,
+			system: mode.is_some(),
+			compressed,
+		}
+	}
+
+	fn encode(header: &StateFileHeader, state: &[u8]) -> Vec<u8> {
+		let mut buf = vec![];
+		write_to(&mut buf, header, ROM_MD5, state).unwrap();
+		buf
+	}
+
+	fn decode_error(data: &[u8]) -> String {
+		match read_from(&mut &data[..]) {
+			Err(AleError::StateDecode(reason)) => reason,
+			Err(e) => panic!("unexpected error: {}", e),
+			Ok(_) => panic!("malformed state file was read"),
+		}
+	}
+
+	#[test]
+	fn round_trip() {
+		let state: Vec<u8> = (0..5000).map(|i| (i % 7) as u8).collect();
+		for &compressed in &[false, true] {
+			for &(mode, difficulty) in &[(None, None), (Some(2), None), (None, Some(1)), (Some(0), Some(3))] {
+				let header = header(compressed, mode, difficulty);
+				let data = encode(&header, &state);
+				assert_eq!(data.len() < HEADER_LEN + state.len(), compressed);
+				assert_eq!(read_from(&mut &data[..]).unwrap(), (header.clone(), state.clone()));
+				assert_eq!(StateFileHeader::read_from(&mut &data[..]).unwrap().0, header);
+			}
+		}
+	}
+
+	#[test]
+	fn rejects_bad_headers() {
+		let data = encode(&header(true, Some(1), None), b"state");
+		assert!(decode_error(&data[..HEADER_LEN - 1]).contains("failed to read header"));
+
+		let mut bad_magic = data.clone();
+		bad_magic[0] = b'X';
+		assert!(decode_error(&bad_magic).contains("not an ALE state file"));
+
+		let mut bad_version = data.clone();
+		bad_version[8..10].copy_from_slice(&(StateFileHeader::VERSION + 1).to_le_bytes());
+		assert!(decode_error(&bad_version).contains("unsupported state file version"));
+
+		for &i in &[10, 27] {
+			let mut unknown_flags = data.clone();
+			unknown_flags[i] |= 0x80;
+			assert!(decode_error(&unknown_flags).contains("unknown flags"));
+		}
+	}
+
+	#[test]
+	fn rejects_length_mismatches() {
+		for &compressed in &[false, true] {
+			let data = encode(&header(compressed, None, None), b"some state");
+			assert!(decode_error(&data[..data.len() - 1]).contains("bytes of state, got"));
+			let mut trailing = data.clone();
+			trailing.push(0);
+			assert!(decode_error(&trailing).contains("bytes of state, got more"));
+		}
+
+		let mut uncompressed = encode(&header(false, None, None), b"some state");
+		uncompressed[60] += 1;
+		assert!(decode_error(&uncompressed).contains("decompressed length of 11"));
+	}
+
+	#[test]
+	fn never_decompresses_beyond_the_recorded_length() {
+		let state = vec![0; 1 << 20];
+		let mut data = encode(&header(true, None, None), &state);
+		data[60..68].copy_from_slice(&1000u64.to_le_bytes());
+		assert_eq!(decode_error(&data), "expected 1000 bytes of decompressed state, got more");
+		data[60..68].copy_from_slice(&((1u64 << 20) + 1).to_le_bytes());
+		assert_eq!(
+			decode_error(&data),
+			format!("expected {} bytes of decompressed state, got {}", (1 << 20) + 1, 1 << 20)
+		);
+	}
+}
@@ -0,0 +1,25 @@
+/// Outcome of a single call to [`Ale::step`](crate::Ale::step).
+///
+/// `terminated` and `truncated` follow the Gymnasium semantics: an episode is *terminated* when the game itself has
+/// ended, and *truncated* when it was cut short by the frame limit. Both can be true at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StepResult {
+	/// Reward received for the action.
+	pub reward: i32,
+	/// Whether the game has ended, i.e. [`Ale::is_game_over`](crate::Ale::is_game_over).
+	pub terminated: bool,
+	/// Whether the episode has reached the maximum number of frames.
+	pub truncated: bool,
+	/// Whether a life was lost during the action.
+	pub life_lost: bool,
+	/// Remaining number of lives.
+	pub lives: i32,
+	/// Frame number since the start of the current episode.
+	pub episode_frame_number: i32,
+	/// Frame number since the loading of the ROM.
+	pub frame_number: i32,
+}
+impl StepResult {
+	/// Whether the episode is over for any reason, i.e. `terminated || truncated`.
+	pub fn done(&self) -> bool { self.terminated || self.truncated }
+}
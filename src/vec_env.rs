@@ -0,0 +1,583 @@
+use rayon::prelude::*;
+
+use crate::{Ale, AleConfig, AleError, BundledRom, Dtype, Environment, Layout, ResetInfo, Result, Space, StepResult};
+
+/// When the environments of a [`VecEnv`] that have finished an episode are reset, mirroring Gymnasium's autoreset
+/// modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AutoReset {
+	/// Environments are reset by the step after the one that finished the episode. That step ignores the action, and
+	/// returns the first observation of the new episode with a reward of `0`.
+	#[default]
+	NextStep,
+	/// Environments are reset by the step that finished the episode, which returns the first observation of the new
+	/// episode. The last observation of the finished episode is available from [`VecEnv::final_observation`].
+	SameStep,
+	/// Environments are never reset automatically, and must be reset with [`VecEnv::reset_env`].
+	Disabled,
+}
+
+/// Information about one environment after a step of a [`VecEnv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EnvInfo {
+	/// Remaining number of lives.
+	pub lives: i32,
+	/// Whether a life was lost during the step.
+	pub life_lost: bool,
+	/// Frame number since the start of the current episode.
+	pub episode_frame_number: i32,
+	/// Frame number since the loading of the ROM.
+	pub frame_number: i32,
+	/// Whether the environment was automatically reset during the step, so that its observation is the first of a new
+	/// episode.
+	pub reset: bool,
+}
+impl EnvInfo {
+	fn step(result: &StepResult, reset: bool) -> EnvInfo {
+		EnvInfo {
+			lives: result.lives,
+			life_lost: result.life_lost,
+			episode_frame_number: result.episode_frame_number,
+			frame_number: result.frame_number,
+			reset,
+		}
+	}
+
+	fn reset(info: &ResetInfo) -> EnvInfo {
+		EnvInfo {
+			lives: info.lives,
+			life_lost: false,
+			episode_frame_number: info.episode_frame_number,
+			frame_number: info.frame_number,
+			reset: true,
+		}
+	}
+}
+
+/// Outcome of a step of every environment of a [`VecEnv`], indexed by environment.
+///
+/// The outcome borrows the buffers of the [`VecEnv`], which are overwritten by the next step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VecStep<'a> {
+	/// Observations after the step, in one contiguous buffer shaped like [`VecEnv::observation_space`].
+	pub observations: &'a [u8],
+	/// Rewards received for the actions.
+	pub rewards: &'a [i32],
+	/// Whether the game has ended, see [`StepResult::terminated`].
+	pub terminated: &'a [bool],
+	/// Whether the episode has reached the maximum number of frames, see [`StepResult::truncated`].
+	pub truncated: &'a [bool],
+	/// Further information about every environment.
+	pub infos: &'a [EnvInfo],
+}
+impl VecStep<'_> {
+	/// Whether the episode of an environment is over for any reason, i.e. `terminated || truncated`.
+	///
+	/// # Panics
+	/// Panics if `index` is not less than the number of environments.
+	pub fn done(&self, index: usize) -> bool { self.terminated[index] || self.truncated[index] }
+}
+
+/// A batch of identically configured environments that are stepped together, in parallel with Rayon.
+///
+/// The environments are [`Ale`]s by default, but any [`Environment`] with byte observations can be batched with
+/// [`VecEnv::from_envs`], e.g. a wrapped [`Ale`]. They are reset automatically once their episodes end, as selected by
+/// [`AutoReset`].
+///
+/// Observations are written into one preallocated buffer of shape `[num_envs, ..observation_shape]`, laid out as
+/// selected by [`VecEnv::set_layout`], which can be handed straight to an inference engine. Rewards, flags and infos
+/// are kept in preallocated arrays alongside it, so that stepping [`Ale`]s makes no Rust heap allocations when it is
+/// called from within the Rayon thread pool, see [`VecEnv::step`]. Only reseeding allocates, as it reloads the ROM,
+/// which is why automatic resets never reseed. Other environments allocate unless they override
+/// [`Environment::observation_into`] and [`Environment::reset_into`].
+///
+/// # Examples
+/// ```
+/// # use ale::{AleConfig, AutoReset, BundledRom, Layout, VecEnv};
+/// let config = AleConfig::new().seed(0).lazy_screen(true);
+/// let mut envs = VecEnv::new(&config, BundledRom::Breakout, 8).unwrap();
+/// envs.set_auto_reset(AutoReset::SameStep);
+/// envs.set_layout(Layout::ChannelsFirst);
+/// assert_eq!(envs.observation_space().shape(), &[8, 3, 210, 160]);
+/// envs.reset(Some(42)).unwrap();
+/// for _ in 0..100 {
+///     let step = envs.step(&[1; 8]).unwrap();
+///     assert_eq!(step.observations.len(), 8 * 3 * 210 * 160);
+///     assert_eq!(step.rewards.len(), 8);
+/// }
+/// ```
+pub struct VecEnv<E = Ale> {
+	envs: Vec<E>,
+	action_dim: usize,
+	auto_reset: AutoReset,
+	layout: Layout,
+	/// Shape of the observation of a single environment, as returned by the environment.
+	env_shape: Vec<usize>,
+	observation_shape: Vec<usize>,
+	/// Observations of the environments before they are transposed into the batch, which are only kept if they are
+	/// transposed.
+	untransposed: Vec<Vec<u8>>,
+	needs_reset: Vec<bool>,
+	observations: Vec<u8>,
+	final_observations: Vec<u8>,
+	has_final_observation: Vec<bool>,
+	rewards: Vec<i32>,
+	terminated: Vec<bool>,
+	truncated: Vec<bool>,
+	infos: Vec<EnvInfo>,
+}
+impl VecEnv<Ale> {
+	/// Creates `num_envs` environments with the given configuration, and loads `rom` into each of them.
+	///
+	/// If the configuration sets a seed, environment `i` is seeded with `seed + i`, so that the environments do not
+	/// all behave the same.
+	///
+	/// # Panics
+	/// Panics if `num_envs` is `0`.
+	pub fn new(config: &AleConfig, rom: BundledRom, num_envs: usize) -> Result<VecEnv<Ale>> {
+		assert!(num_envs > 0, "num_envs must be at least 1");
+		let envs = (0..num_envs)
+			.into_par_iter()
+			.map(|i| match config.seed {
+				Some(seed) => config.clone().seed(seed.wrapping_add(i as i32)).build(rom),
+				None => config.build(rom),
+			})
+			.collect::<Result<Vec<_>>>()?;
+		VecEnv::from_envs(envs)
+	}
+}
+impl<E: Environment<Observation = Vec<u8>> + Send> VecEnv<E> {
+	/// Batches environments that all have the same observation and action spaces.
+	///
+	/// Returns [`AleError::UnsupportedSpace`] if the observations are not bytes, if the actions are not discrete, or if
+	/// an environment has other spaces than the first one.
+	///
+	/// # Panics
+	/// Panics if `envs` is empty.
+	pub fn from_envs(envs: Vec<E>) -> Result<VecEnv<E>> {
+		assert!(!envs.is_empty(), "envs must not be empty");
+		let (observation_space, action_space) = (envs[0].observation_space(), envs[0].action_space());
+		if !matches!(observation_space, Space::Box { dtype: Dtype::U8, .. }) {
+			return Err(AleError::UnsupportedSpace(observation_space));
+		}
+		let action_dim = match action_space {
+			Space::Discrete { n } => n,
+			space => return Err(AleError::UnsupportedSpace(space)),
+		};
+		for env in &envs[1..] {
+			let (observation, action) = (env.observation_space(), env.action_space());
+			if observation != observation_space {
+				return Err(AleError::UnsupportedSpace(observation));
+			}
+			if action != (Space::Discrete { n: action_dim }) {
+				return Err(AleError::UnsupportedSpace(action));
+			}
+		}
+		let num_envs = envs.len();
+		let observation_len = observation_space.len();
+		let mut vec_env = VecEnv {
+			envs,
+			action_dim,
+			auto_reset: AutoReset::default(),
+			layout: Layout::default(),
+			env_shape: observation_space.shape().to_vec(),
+			observation_shape: vec![],
+			untransposed: vec![vec![]; num_envs],
+			needs_reset: vec![false; num_envs],
+			observations: vec![0; num_envs * observation_len],
+			final_observations: vec![0; num_envs * observation_len],
+			has_final_observation: vec![false; num_envs],
+			rewards: vec![0; num_envs],
+			terminated: vec![false; num_envs],
+			truncated: vec![false; num_envs],
+			infos: vec![EnvInfo::default(); num_envs],
+		};
+		vec_env.set_layout(Layout::default());
+		Ok(vec_env)
+	}
+
+	/// Returns the number of environments.
+	pub fn num_envs(&self) -> usize {
+		self.envs.len()
+	}
+
+	/// Returns the environments.
+	pub fn envs(&self) -> &[E] {
+		&self.envs
+	}
+
+	/// Returns the environments mutably.
+	///
+	/// Changing their observation or action spaces is not supported, e.g. the observation type or action set of an
+	/// [`Ale`], as all environments must keep the shape of their observations and the number of their actions.
+	pub fn envs_mut(&mut self) -> &mut [E] {
+		&mut self.envs
+	}
+
+	/// Returns when environments that have finished an episode are reset.
+	pub fn auto_reset(&self) -> AutoReset {
+		self.auto_reset
+	}
+
+	/// Sets when environments that have finished an episode are reset. Defaults to [`AutoReset::NextStep`].
+	pub fn set_auto_reset(&mut self, auto_reset: AutoReset) {
+		self.auto_reset = auto_reset;
+	}
+
+	/// Returns the layout of image observations in the batch.
+	pub fn layout(&self) -> Layout {
+		self.layout
+	}
+
+	/// Sets the layout of image observations in the batch, i.e. NHWC for [`Layout::ChannelsLast`] or NCHW for
+	/// [`Layout::ChannelsFirst`]. Defaults to [`Layout::ChannelsLast`], the layout of [`Ale::observation`].
+	///
+	/// With [`Layout::ChannelsFirst`], grayscale and palette observations get a channel dimension of size `1`. RAM
+	/// observations are not images, and are not affected.
+	///
+	/// The current observations are rewritten in the new layout.
+	pub fn set_layout(&mut self, layout: Layout) {
+		self.layout = layout;
+		self.observation_shape = match (layout, &self.env_shape[..]) {
+			(Layout::ChannelsFirst, &[height, width]) => vec![1, height, width],
+			(Layout::ChannelsFirst, &[height, width, channels]) => vec![channels, height, width],
+			(_, shape) => shape.to_vec(),
+		};
+		let (transpose, observation_len) = (self.transpose(), self.observation_len());
+		for untransposed in &mut self.untransposed {
+			untransposed.resize(if transpose.is_some() { observation_len } else { 0 }, 0);
+		}
+		self.envs
+			.par_iter_mut()
+			.zip(self.untransposed.par_iter_mut())
+			.zip(self.observations.par_chunks_mut(observation_len))
+			.for_each(|((env, untransposed), observation)| {
+				write_observation(env, transpose, untransposed, observation)
+			});
+		self.has_final_observation.iter_mut().for_each(|has_final_observation| *has_final_observation = false);
+	}
+
+	/// Returns the shape of the observation of a single environment, in the layout of the batch.
+	pub fn observation_shape(&self) -> &[usize] {
+		&self.observation_shape
+	}
+
+	/// Returns the space of the observation of a single environment, in the layout of the batch.
+	pub fn single_observation_space(&self) -> Space {
+		Space::bytes(self.observation_shape.clone())
+	}
+
+	/// Returns the space of the batched observations, of shape `[num_envs, ..observation_shape]`.
+	pub fn observation_space(&self) -> Space {
+		let mut shape = vec![self.envs.len()];
+		shape.extend_from_slice(&self.observation_shape);
+		Space::bytes(shape)
+	}
+
+	/// Returns the space of the actions of a single environment.
+	pub fn single_action_space(&self) -> Space {
+		Space::Discrete { n: self.action_dim }
+	}
+
+	/// Returns the current observations, shaped like [`VecEnv::observation_space`].
+	pub fn observations(&self) -> &[u8] {
+		&self.observations
+	}
+
+	/// Returns the current observation of a single environment, shaped like [`VecEnv::observation_shape`].
+	///
+	/// # Panics
+	/// Panics if `index` is not less than [`VecEnv::num_envs`].
+	pub fn observation(&self, index: usize) -> &[u8] {
+		let observation_len = self.observation_len();
+		&self.observations[index * observation_len..][..observation_len]
+	}
+
+	/// Resets every environment, and returns their first observations, shaped like [`VecEnv::observation_space`].
+	///
+	/// If `seed` is given, environment `i` is reset with the seed `seed + i`, see [`Environment::reset`].
+	pub fn reset(&mut self, seed: Option<i32>) -> Result<(&[u8], Vec<ResetInfo>)> {
+		let (transpose, observation_len) = (self.transpose(), self.observation_len());
+		let infos = self
+			.envs
+			.par_iter_mut()
+			.zip(self.untransposed.par_iter_mut())
+			.zip(self.observations.par_chunks_mut(observation_len))
+			.enumerate()
+			.map(|(i, ((env, untransposed), observation))| {
+				let seed = seed.map(|seed| seed.wrapping_add(i as i32));
+				reset(env, seed, transpose, untransposed, observation)
+			})
+			.collect::<Result<Vec<_>>>()?;
+		self.needs_reset.iter_mut().for_each(|needs_reset| *needs_reset = false);
+		self.has_final_observation.iter_mut().for_each(|has_final_observation| *has_final_observation = false);
+		Ok((&self.observations, infos))
+	}
+
+	/// Resets a single environment, optionally with a seed, and returns its first observation.
+	///
+	/// For [`Ale`]s, this only allocates if a seed is given, as reseeding reloads the ROM, see [`Ale::reseed`].
+	///
+	/// # Panics
+	/// Panics if `index` is not less than [`VecEnv::num_envs`].
+	pub fn reset_env(&mut self, index: usize, seed: Option<i32>) -> Result<(&[u8], ResetInfo)> {
+		let (transpose, observation_len) = (self.transpose(), self.observation_len());
+		let observation = &mut self.observations[index * observation_len..][..observation_len];
+		let info = reset(&mut self.envs[index], seed, transpose, &mut self.untransposed[index], observation)?;
+		self.needs_reset[index] = false;
+		self.has_final_observation[index] = false;
+		Ok((observation, info))
+	}
+
+	/// Applies an action to every environment, resetting them as selected by [`VecEnv::set_auto_reset`].
+	///
+	/// The observations and outcomes are written into the buffers of the [`VecEnv`]. When called from within the Rayon
+	/// thread pool that steps the environments, this makes no Rust heap allocations for [`Ale`]s unless an error is
+	/// returned, and environments are reset without being reseeded, so this holds for automatic resets as well. When
+	/// called from outside the pool, Rayon may allocate to hand the work to it. Memory that the ALE allocates in C++ is
+	/// not covered, as it does not go through Rust's allocator.
+	///
+	/// Returns [`AleError::InvalidAction`] if any action is not an element of [`VecEnv::single_action_space`], before
+	/// any environment is stepped. Environments may have been stepped if one of them returns an error.
+	///
+	/// # Panics
+	/// Panics if the number of actions is not [`VecEnv::num_envs`].
+	pub fn step(&mut self, actions: &[u8]) -> Result<VecStep<'_>> {
+		assert_eq!(actions.len(), self.envs.len(), "expected one action per environment");
+		if let Some(&index) = actions.iter().find(|&&action| usize::from(action) >= self.action_dim) {
+			// Actions are bytes, so an invalid one means that there are fewer than 256 actions
+			return Err(AleError::InvalidAction { index, action_dim: self.action_dim as u8 });
+		}
+		let (auto_reset, transpose, observation_len) = (self.auto_reset, self.transpose(), self.observation_len());
+		let buffers = (
+			self.untransposed.par_iter_mut(),
+			self.observations.par_chunks_mut(observation_len),
+			self.final_observations.par_chunks_mut(observation_len),
+			self.has_final_observation.par_iter_mut(),
+		);
+		let outputs = (
+			self.rewards.par_iter_mut(),
+			self.terminated.par_iter_mut(),
+			self.truncated.par_iter_mut(),
+			self.infos.par_iter_mut(),
+		);
+		(self.envs.par_iter_mut(), actions.par_iter(), self.needs_reset.par_iter_mut(), buffers, outputs)
+			.into_par_iter()
+			.try_for_each(|(env, &action, needs_reset, buffers, outputs)| -> Result<()> {
+				let (untransposed, observation, final_observation, has_final_observation) = buffers;
+				let (reward, terminated, truncated, info) = outputs;
+				*has_final_observation = false;
+				let result = if *needs_reset {
+					*needs_reset = false;
+					*info = EnvInfo::reset(&reset(env, None, transpose, untransposed, observation)?);
+					StepResult::default()
+				} else {
+					let result = env.step(action)?;
+					*info = EnvInfo::step(&result, false);
+					if result.done() && auto_reset == AutoReset::SameStep {
+						write_observation(env, transpose, untransposed, final_observation);
+						*has_final_observation = true;
+						reset(env, None, transpose, untransposed, observation)?;
+						info.reset = true;
+					} else {
+						*needs_reset = result.done() && auto_reset == AutoReset::NextStep;
+						write_observation(env, transpose, untransposed, observation);
+					}
+					result
+				};
+				*reward = result.reward;
+				*terminated = result.terminated;
+				*truncated = result.truncated;
+				Ok(())
+			})?;
+		Ok(VecStep {
+			observations: &self.observations,
+			rewards: &self.rewards,
+			terminated: &self.terminated,
+			truncated: &self.truncated,
+			infos: &self.infos,
+		})
+	}
+
+	/// Returns the last observation of the episode an environment finished in the last step, if it was reset by that
+	/// step with [`AutoReset::SameStep`].
+	///
+	/// # Panics
+	/// Panics if `index` is not less than [`VecEnv::num_envs`].
+	pub fn final_observation(&self, index: usize) -> Option<&[u8]> {
+		if !self.has_final_observation[index] {
+			return None;
+		}
+		let observation_len = self.observation_len();
+		Some(&self.final_observations[index * observation_len..][..observation_len])
+	}
+
+	fn observation_len(&self) -> usize {
+		self.observation_shape.iter().product()
+	}
+
+	/// Returns the shape of the images that are transposed into the batch, if the layout is channels first.
+	fn transpose(&self) -> Option<[usize; 3]> {
+		match (self.layout, &self.env_shape[..]) {
+			(Layout::ChannelsFirst, &[height, width, channels]) => Some([height, width, channels]),
+			_ => None,
+		}
+	}
+}
+
+/// Resets `env` with [`Environment::reset_into`], and writes its first observation into `out` like
+/// [`write_observation`].
+fn reset<E: Environment<Observation = Vec<u8>>>(
+	env: &mut E,
+	seed: Option<i32>,
+	transpose: Option<[usize; 3]>,
+	untransposed: &mut [u8],
+	out: &mut [u8],
+) -> Result<ResetInfo> {
+	match transpose {
+		Some(shape) => {
+			let info = env.reset_into(seed, untransposed)?;
+			Layout::ChannelsFirst.write_stacked(untransposed, shape, 0, 1, out);
+			Ok(info)
+		}
+		None => env.reset_into(seed, out),
+	}
+}
+
+/// Writes the current observation of `env` into `out`, transposing images of shape `transpose` to channels first
+/// through `untransposed`.
+fn write_observation<E: Environment<Observation = Vec<u8>>>(
+	env: &mut E,
+	transpose: Option<[usize; 3]>,
+	untransposed: &mut [u8],
+	out: &mut [u8],
+) {
+	match transpose {
+		Some(shape) => {
+			env.observation_into(untransposed);
+			Layout::ChannelsFirst.write_stacked(untransposed, shape, 0, 1, out);
+		}
+		None => env.observation_into(out),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// An environment whose episodes end after `episode_len` steps, each rewarded with `1`. Observations are RGB images
+	/// whose channel `c` takes the value `10 * steps + c`.
+	struct FakeEnv {
+		episode_len: usize,
+		steps: usize,
+		resets: usize,
+		actions: Vec<u8>,
+	}
+	impl FakeEnv {
+		fn new(episode_len: usize) -> FakeEnv {
+			FakeEnv { episode_len, steps: 0, resets: 0, actions: vec![] }
+		}
+	}
+	impl Environment for FakeEnv {
+		type Observation = Vec<u8>;
+
+		fn reset(&mut self, _seed: Option<i32>) -> Result<(Vec<u8>, ResetInfo)> {
+			self.steps = 0;
+			self.resets += 1;
+			Ok((self.observation(), ResetInfo::default()))
+		}
+
+		fn step(&mut self, action: u8) -> Result<StepResult> {
+			self.steps += 1;
+			self.actions.push(action);
+			Ok(StepResult { reward: 1, terminated: self.steps >= self.episode_len, ..StepResult::default() })
+		}
+
+		fn observation(&mut self) -> Vec<u8> { (0..2 * 2 * 3).map(|i| (10 * self.steps + i % 3) as u8).collect() }
+
+		fn observation_space(&self) -> Space { Space::bytes(vec![2, 2, 3]) }
+
+		fn action_space(&self) -> Space { Space::Discrete { n: 3 } }
+	}
+
+	fn vec_env(auto_reset: AutoReset) -> VecEnv<FakeEnv> {
+		let mut envs = VecEnv::from_envs(vec![FakeEnv::new(2), FakeEnv::new(3)]).unwrap();
+		envs.set_auto_reset(auto_reset);
+		envs.reset(None).unwrap();
+		envs
+	}
+
+	fn observation(steps: usize) -> Vec<u8> { FakeEnv { steps, ..FakeEnv::new(0) }.observation() }
+
+	#[test]
+	fn next_step_resets_in_the_following_step() {
+		let mut envs = vec_env(AutoReset::NextStep);
+		envs.step(&[1, 1]).unwrap();
+		let step = envs.step(&[2, 2]).unwrap();
+		assert_eq!((step.rewards, step.terminated), (&[1, 1][..], &[true, false][..]));
+		assert!(!step.infos[0].reset);
+		assert_eq!(envs.observation(0), &observation(2)[..]);
+
+		let step = envs.step(&[1, 2]).unwrap();
+		assert_eq!((step.rewards, step.terminated), (&[0, 1][..], &[false, true][..]));
+		assert_eq!((step.infos[0].reset, step.infos[1].reset), (true, false));
+		assert_eq!(envs.observation(0), &observation(0)[..]);
+		assert_eq!(envs.final_observation(0), None);
+		// The action of the resetting step is ignored
+		assert_eq!(envs.envs()[0].actions, [1, 2]);
+		assert_eq!(envs.envs()[0].resets, 2);
+	}
+
+	#[test]
+	fn same_step_keeps_the_final_observation() {
+		let mut envs = vec_env(AutoReset::SameStep);
+		envs.step(&[1, 1]).unwrap();
+		let step = envs.step(&[2, 2]).unwrap();
+		assert_eq!((step.rewards, step.terminated), (&[1, 1][..], &[true, false][..]));
+		assert_eq!((step.infos[0].reset, step.infos[1].reset), (true, false));
+		assert_eq!(envs.observation(0), &observation(0)[..]);
+		assert_eq!(envs.final_observation(0), Some(&observation(2)[..]));
+		assert_eq!(envs.final_observation(1), None);
+		assert_eq!(envs.envs()[0].resets, 2);
+
+		let step = envs.step(&[1, 1]).unwrap();
+		assert_eq!((step.rewards, step.infos[0].reset), (&[1, 1][..], false));
+		assert_eq!(envs.final_observation(0), None);
+		assert_eq!(envs.envs()[0].actions, [1, 2, 1]);
+	}
+
+	#[test]
+	fn disabled_never_resets() {
+		let mut envs = vec_env(AutoReset::Disabled);
+		for steps in 1..=4 {
+			let step = envs.step(&[1, 1]).unwrap();
+			assert_eq!(step.rewards, &[1, 1]);
+			assert_eq!(step.terminated, &[steps >= 2, steps >= 3]);
+			assert!(step.infos.iter().all(|info| !info.reset));
+			assert_eq!(envs.final_observation(0), None);
+		}
+		assert!(envs.envs().iter().all(|env| env.resets == 1 && env.steps == 4));
+		let (first, _) = envs.reset_env(0, None).unwrap();
+		assert_eq!(first, &observation(0)[..]);
+		assert_eq!(envs.envs()[0].resets, 2);
+	}
+
+	#[test]
+	fn rejects_invalid_actions_before_stepping() {
+		let mut envs = vec_env(AutoReset::NextStep);
+		assert!(matches!(envs.step(&[0, 3]), Err(AleError::InvalidAction { index: 3, action_dim: 3 })));
+		assert!(envs.envs().iter().all(|env| env.steps == 0));
+	}
+
+	#[test]
+	fn transposes_images_to_channels_first() {
+		let mut envs = vec_env(AutoReset::SameStep);
+		envs.set_layout(Layout::ChannelsFirst);
+		assert_eq!(envs.observation_shape(), [3, 2, 2]);
+		envs.step(&[0, 0]).unwrap();
+		assert_eq!(envs.observation(1), [10, 10, 10, 10, 11, 11, 11, 11, 12, 12, 12, 12]);
+		envs.step(&[0, 0]).unwrap();
+		assert_eq!(envs.observation(0), [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2]);
+		assert_eq!(envs.final_observation(0), Some(&[20, 20, 20, 20, 21, 21, 21, 21, 22, 22, 22, 22][..]));
+	}
+}
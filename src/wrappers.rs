@@ -0,0 +1,990 @@
+//! Wrappers that change the behaviour of an [`Environment`], e.g. to preprocess its observations like DQN does.
+//!
+//! Each wrapper is an [`Environment`] itself, so they can be composed freely. [`AtariPreprocessing`] composes them into
+//! the standard preprocessing of Atari games.
+//!
+//! Wrappers that step the environment on their own, like [`NoopReset`] and [`FireReset`], assume that action `0` is
+//! `NOOP` and action `1` is `FIRE`, which is the case for the full action set and for the minimal action set of games
+//! that use the fire button. [`FireReset`] checks this with [`Environment::action_meanings`].
+//!
+//! # Examples
+//! ```
+//! # use ale::{AleConfig, BundledRom, Environment};
+//! use ale::wrappers::{ClipReward, FireReset, GrayScale, MaxAndSkip, NoopReset, ResizeObservation};
+//!
+//! let ale = AleConfig::new().build(BundledRom::Breakout).unwrap();
+//! let env = FireReset::new(NoopReset::new(ale, 30)).unwrap();
+//! let env = MaxAndSkip::new(GrayScale::new(env).unwrap(), 4);
+//! let mut env = ClipReward::new(ResizeObservation::new(env, 84, 84).unwrap());
+//! let (observation, _) = env.reset(Some(42)).unwrap();
+//! assert_eq!(observation.len(), 84 * 84);
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::palette::luminance;
+use crate::{Action, AleError, Environment, Layout, ResetInfo, Result, Space, StepResult};
+
+/// Implements accessors for the environment wrapped in the `env` field.
+macro_rules! impl_inner {
+	($wrapper:ident) => {
+		impl<E> $wrapper<E> {
+			/// Returns the wrapped environment.
+			pub fn inner(&self) -> &E {
+				&self.env
+			}
+
+			/// Returns the wrapped environment mutably.
+			pub fn inner_mut(&mut self) -> &mut E {
+				&mut self.env
+			}
+
+			/// Unwraps the wrapped environment.
+			pub fn into_inner(self) -> E {
+				self.env
+			}
+		}
+	};
+}
+
+/// Takes a random number of `NOOP` actions after every reset, between `1` and `noop_max`, so that episodes start from
+/// different states.
+///
+/// The number of actions is drawn from a generator that is reseeded whenever the environment is, and that starts from
+/// a fixed seed like the ALE does, so episodes are reproducible whether or not a seed is given.
+pub struct NoopReset<E> {
+	env: E,
+	noop_max: u32,
+	rng: StdRng,
+}
+impl<E: Environment> NoopReset<E> {
+	/// Wraps `env`. No actions are taken if `noop_max` is `0`.
+	pub fn new(env: E, noop_max: u32) -> NoopReset<E> {
+		NoopReset { env, noop_max, rng: StdRng::seed_from_u64(0) }
+	}
+}
+impl_inner!(NoopReset);
+impl<E: Environment> Environment for NoopReset<E> {
+	type Observation = E::Observation;
+
+	/// Resets the environment and takes the `NOOP` actions, resetting again if the episode ends in the meantime.
+	///
+	/// The number of actions is reseeded along with the environment.
+	fn reset(&mut self, seed: Option<i32>) -> Result<(E::Observation, ResetInfo)> {
+		if let Some(seed) = seed {
+			self.rng = StdRng::seed_from_u64(seed as u64);
+		}
+		let (observation, mut info) = self.env.reset(seed)?;
+		if self.noop_max == 0 {
+			return Ok((observation, info));
+		}
+		for _ in 0..self.rng.gen_range(1..=self.noop_max) {
+			let result = self.env.step(0)?;
+			info = ResetInfo::from(&result);
+			if result.done() {
+				info = self.env.reset(None)?.1;
+			}
+		}
+		Ok((self.env.observation(), info))
+	}
+
+	fn step(&mut self, action: u8) -> Result<StepResult> { self.env.step(action) }
+
+	fn observation(&mut self) -> E::Observation { self.env.observation() }
+
+	fn observation_into(&mut self, out: &mut [u8])
+	where
+		E::Observation: AsRef<[u8]>,
+	{
+		self.env.observation_into(out)
+	}
+
+	fn observation_space(&self) -> Space { self.env.observation_space() }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Presses `FIRE` after every reset, for games that only start once it is pressed.
+pub struct FireReset<E> {
+	env: E,
+}
+impl<E: Environment> FireReset<E> {
+	/// Wraps `env`.
+	///
+	/// Returns [`AleError::NoFireAction`] if action `1` of the environment is not `FIRE`, according to
+	/// [`Environment::action_meanings`], or if it has fewer than 3 actions. If the meanings of the actions are not
+	/// known, only the number of actions can be checked.
+	pub fn new(env: E) -> Result<FireReset<E>> {
+		let action_dim = match env.action_space() {
+			Space::Discrete { n } => n,
+			space => return Err(AleError::UnsupportedSpace(space)),
+		};
+		let action = env.action_meanings().and_then(|meanings| meanings.get(1).copied());
+		if action_dim < 3 || (env.action_meanings().is_some() && action != Some(Action::Fire)) {
+			return Err(AleError::NoFireAction { action_dim, action });
+		}
+		Ok(FireReset { env })
+	}
+}
+impl_inner!(FireReset);
+impl<E: Environment> Environment for FireReset<E> {
+	type Observation = E::Observation;
+
+	/// Resets the environment and takes actions `1` (`FIRE`) and `2`, like OpenAI's baselines do, resetting again if
+	/// the episode ends in the meantime.
+	fn reset(&mut self, seed: Option<i32>) -> Result<(E::Observation, ResetInfo)> {
+		let (_, mut info) = self.env.reset(seed)?;
+		for &action in &[1, 2] {
+			let result = self.env.step(action)?;
+			info = ResetInfo::from(&result);
+			if result.done() {
+				info = self.env.reset(None)?.1;
+			}
+		}
+		Ok((self.env.observation(), info))
+	}
+
+	fn step(&mut self, action: u8) -> Result<StepResult> { self.env.step(action) }
+
+	fn observation(&mut self) -> E::Observation { self.env.observation() }
+
+	fn observation_into(&mut self, out: &mut [u8])
+	where
+		E::Observation: AsRef<[u8]>,
+	{
+		self.env.observation_into(out)
+	}
+
+	fn observation_space(&self) -> Space { self.env.observation_space() }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Repeats every action for `skip` steps, summing the rewards, and observes the pixel-wise maximum of the last two
+/// observations, which removes the flickering of sprites that are only drawn every other frame.
+///
+/// The wrapped environment should not skip frames itself, i.e. its `frame_skip` setting should be `1`.
+pub struct MaxAndSkip<E> {
+	env: E,
+	skip: u32,
+	observation: Vec<u8>,
+}
+impl<E: Environment<Observation = Vec<u8>>> MaxAndSkip<E> {
+	/// Wraps `env`.
+	///
+	/// # Panics
+	/// Panics if `skip` is `0`.
+	pub fn new(env: E, skip: u32) -> MaxAndSkip<E> {
+		assert!(skip > 0, "skip must be at least 1");
+		MaxAndSkip { env, skip, observation: vec![] }
+	}
+}
+impl_inner!(MaxAndSkip);
+impl<E: Environment<Observation = Vec<u8>>> Environment for MaxAndSkip<E> {
+	type Observation = Vec<u8>;
+
+	fn reset(&mut self, seed: Option<i32>) -> Result<(Vec<u8>, ResetInfo)> {
+		let (observation, info) = self.env.reset(seed)?;
+		self.observation.clone_from(&observation);
+		Ok((observation, info))
+	}
+
+	/// Repeats the action until it has been taken `skip` times or the episode is over.
+	///
+	/// The returned result has the summed reward, loses a life if any step did, and is otherwise that of the last step.
+	fn step(&mut self, action: u8) -> Result<StepResult> {
+		let mut reward = 0;
+		let mut life_lost = false;
+		let mut last = StepResult::default();
+		let mut previous = None;
+		for i in 0..self.skip {
+			last = self.env.step(action)?;
+			reward += last.reward;
+			life_lost |= last.life_lost;
+			if i + 2 == self.skip {
+				previous = Some(self.env.observation());
+			}
+			if last.done() {
+				break;
+			}
+		}
+		self.observation = self.env.observation();
+		if let Some(previous) = previous {
+			for (pixel, previous) in self.observation.iter_mut().zip(previous) {
+				*pixel = (*pixel).max(previous);
+			}
+		}
+		Ok(StepResult { reward, life_lost, ..last })
+	}
+
+	fn observation(&mut self) -> Vec<u8> { self.observation.clone() }
+
+	fn observation_into(&mut self, out: &mut [u8]) { out.copy_from_slice(&self.observation) }
+
+	fn observation_space(&self) -> Space { self.env.observation_space() }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Resizes image observations, averaging the pixels that each output pixel covers (like OpenCV's `INTER_AREA`).
+///
+/// Observations must be images of shape `[height, width]` or `[height, width, channels]`.
+pub struct ResizeObservation<E> {
+	env: E,
+	shape: Vec<usize>,
+	in_width: usize,
+	rows: Vec<Vec<(usize, f32)>>,
+	columns: Vec<Vec<(usize, f32)>>,
+}
+impl<E: Environment<Observation = Vec<u8>>> ResizeObservation<E> {
+	/// Wraps `env`, resizing its observations to `height` by `width` pixels.
+	///
+	/// Returns [`AleError::UnsupportedSpace`] if the observations are not images.
+	pub fn new(env: E, height: usize, width: usize) -> Result<ResizeObservation<E>> {
+		let space = env.observation_space();
+		let mut shape = match &space {
+			Space::Box { shape, .. } if shape.len() == 2 || shape.len() == 3 => shape.clone(),
+			_ => return Err(AleError::UnsupportedSpace(space)),
+		};
+		let in_width = shape[1];
+		let rows = area_weights(shape[0], height);
+		let columns = area_weights(in_width, width);
+		shape[0] = height;
+		shape[1] = width;
+		Ok(ResizeObservation { env, shape, in_width, rows, columns })
+	}
+
+	fn resize(&self, observation: &[u8]) -> Vec<u8> {
+		let channels = self.shape.get(2).copied().unwrap_or(1);
+		let mut out = Vec::with_capacity(self.shape.iter().product());
+		let mut sums = vec![0.0f32; channels];
+		for row in &self.rows {
+			for column in &self.columns {
+				sums.iter_mut().for_each(|sum| *sum = 0.0);
+				for &(y, wy) in row {
+					for &(x, wx) in column {
+						let pixel = &observation[(y * self.in_width + x) * channels..][..channels];
+						for (sum, &value) in sums.iter_mut().zip(pixel) {
+							*sum += wy * wx * value as f32;
+						}
+					}
+				}
+				out.extend(sums.iter().map(|&sum| sum.round().min(255.0) as u8));
+			}
+		}
+		out
+	}
+}
+impl_inner!(ResizeObservation);
+impl<E: Environment<Observation = Vec<u8>>> Environment for ResizeObservation<E> {
+	type Observation = Vec<u8>;
+
+	fn reset(&mut self, seed: Option<i32>) -> Result<(Vec<u8>, ResetInfo)> {
+		let (observation, info) = self.env.reset(seed)?;
+		Ok((self.resize(&observation), info))
+	}
+
+	fn step(&mut self, action: u8) -> Result<StepResult> { self.env.step(action) }
+
+	fn observation(&mut self) -> Vec<u8> {
+		let observation = self.env.observation();
+		self.resize(&observation)
+	}
+
+	fn observation_space(&self) -> Space { Space::bytes(self.shape.clone()) }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Returns, for each of `dst` output pixels, the input pixels it covers when resizing from `src` pixels, along with the
+/// fraction of the output pixel each of them covers.
+fn area_weights(src: usize, dst: usize) -> Vec<Vec<(usize, f32)>> {
+	let scale = src as f32 / dst as f32;
+	(0..dst)
+		.map(|i| {
+			let (start, end) = (i as f32 * scale, (i + 1) as f32 * scale);
+			(start.floor() as usize..(end.ceil() as usize).min(src))
+				.map(|j| (j, (end.min(j as f32 + 1.0) - start.max(j as f32)) / scale))
+				.filter(|&(_, weight)| weight > 0.0)
+				.collect()
+		})
+		.collect()
+}
+
+/// Converts RGB observations of shape `[height, width, 3]` to grayscale observations of shape `[height, width]`.
+///
+/// When wrapping an [`Ale`](crate::Ale) directly, setting its observation type to
+/// [`ObservationType::Grayscale`](crate::ObservationType::Grayscale) gives the same result without the conversion.
+pub struct GrayScale<E> {
+	env: E,
+	shape: Vec<usize>,
+}
+impl<E: Environment<Observation = Vec<u8>>> GrayScale<E> {
+	/// Wraps `env`.
+	///
+	/// Returns [`AleError::UnsupportedSpace`] if the observations are not RGB images.
+	pub fn new(env: E) -> Result<GrayScale<E>> {
+		match env.observation_space() {
+			Space::Box { shape, .. } if shape.len() == 3 && shape[2] == 3 => {
+				Ok(GrayScale { env, shape: shape[..2].to_vec() })
+			}
+			space => Err(AleError::UnsupportedSpace(space)),
+		}
+	}
+}
+impl_inner!(GrayScale);
+impl<E: Environment<Observation = Vec<u8>>> Environment for GrayScale<E> {
+	type Observation = Vec<u8>;
+
+	fn reset(&mut self, seed: Option<i32>) -> Result<(Vec<u8>, ResetInfo)> {
+		let (observation, info) = self.env.reset(seed)?;
+		Ok((to_grayscale(&observation), info))
+	}
+
+	fn step(&mut self, action: u8) -> Result<StepResult> { self.env.step(action) }
+
+	fn observation(&mut self) -> Vec<u8> { to_grayscale(&self.env.observation()) }
+
+	fn observation_space(&self) -> Space { Space::bytes(self.shape.clone()) }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+fn to_grayscale(rgb: &[u8]) -> Vec<u8> {
+	rgb.chunks_exact(3).map(|pixel| luminance([pixel[0], pixel[1], pixel[2]])).collect()
+}
+
+/// Clips rewards to their sign, i.e. `-1`, `0` or `1`.
+pub struct ClipReward<E> {
+	env: E,
+}
+impl<E: Environment> ClipReward<E> {
+	/// Wraps `env`.
+	pub fn new(env: E) -> ClipReward<E> {
+		ClipReward { env }
+	}
+}
+impl_inner!(ClipReward);
+impl<E: Environment> Environment for ClipReward<E> {
+	type Observation = E::Observation;
+
+	fn reset(&mut self, seed: Option<i32>) -> Result<(E::Observation, ResetInfo)> { self.env.reset(seed) }
+
+	fn step(&mut self, action: u8) -> Result<StepResult> {
+		let result = self.env.step(action)?;
+		Ok(StepResult { reward: result.reward.signum(), ..result })
+	}
+
+	fn observation(&mut self) -> E::Observation { self.env.observation() }
+
+	fn observation_into(&mut self, out: &mut [u8])
+	where
+		E::Observation: AsRef<[u8]>,
+	{
+		self.env.observation_into(out)
+	}
+
+	fn observation_space(&self) -> Space { self.env.observation_space() }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Ends episodes when a life is lost, but only resets the game once it is really over.
+///
+/// This makes the loss of a life a terminal state for value estimation, while still playing through all lives.
+pub struct EpisodicLife<E> {
+	env: E,
+	game_over: bool,
+}
+impl<E: Environment> EpisodicLife<E> {
+	/// Wraps `env`.
+	pub fn new(env: E) -> EpisodicLife<E> {
+		EpisodicLife { env, game_over: true }
+	}
+
+	/// Returns whether the game is really over, rather than a life having been lost.
+	pub fn is_game_over(&self) -> bool {
+		self.game_over
+	}
+}
+impl_inner!(EpisodicLife);
+impl<E: Environment> Environment for EpisodicLife<E> {
+	type Observation = E::Observation;
+
+	/// Resets the game if it is over or a seed is given. Otherwise only takes a `NOOP` action, to continue from the
+	/// state after the life was lost.
+	fn reset(&mut self, seed: Option<i32>) -> Result<(E::Observation, ResetInfo)> {
+		if !self.game_over && seed.is_none() {
+			let result = self.env.step(0)?;
+			if !result.done() {
+				return Ok((self.env.observation(), ResetInfo::from(&result)));
+			}
+		}
+		self.game_over = false;
+		self.env.reset(seed)
+	}
+
+	/// Steps the environment, and reports the episode as terminated if a life was lost.
+	fn step(&mut self, action: u8) -> Result<StepResult> {
+		let result = self.env.step(action)?;
+		self.game_over = result.done();
+		Ok(StepResult { terminated: result.terminated || (result.life_lost && result.lives > 0), ..result })
+	}
+
+	fn observation(&mut self) -> E::Observation { self.env.observation() }
+
+	fn observation_into(&mut self, out: &mut [u8])
+	where
+		E::Observation: AsRef<[u8]>,
+	{
+		self.env.observation_into(out)
+	}
+
+	fn observation_space(&self) -> Space { self.env.observation_space() }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Stacks the last `num_stack` observations along the channel dimension, as [`LazyFrames`] that share their frames.
+///
+/// Observations must be images of shape `[height, width]` or `[height, width, channels]`. After a reset, the stack is
+/// filled with copies of the first observation.
+///
+/// # Examples
+/// ```
+/// # use ale::{AleConfig, BundledRom, Environment, Layout, ObservationType};
+/// use ale::wrappers::FrameStack;
+///
+/// let ale = AleConfig::new().observation_type(ObservationType::Grayscale).build(BundledRom::Breakout).unwrap();
+/// let mut env = FrameStack::new(ale, 4, Layout::ChannelsFirst).unwrap();
+/// let (frames, _) = env.reset(Some(0)).unwrap();
+/// assert_eq!(frames.shape(), vec![4, 210, 160]);
+/// let observation: Vec<u8> = frames.to_vec();
+/// # assert_eq!(observation.len(), 4 * 210 * 160);
+/// ```
+pub struct FrameStack<E> {
+	env: E,
+	frames: VecDeque<Arc<[u8]>>,
+	num_stack: usize,
+	frame_shape: [usize; 3],
+	layout: Layout,
+}
+impl<E: Environment<Observation = Vec<u8>>> FrameStack<E> {
+	/// Wraps `env`, stacking `num_stack` observations in the given layout.
+	///
+	/// Returns [`AleError::UnsupportedSpace`] if the observations are not images.
+	///
+	/// # Panics
+	/// Panics if `num_stack` is `0`.
+	pub fn new(env: E, num_stack: usize, layout: Layout) -> Result<FrameStack<E>> {
+		assert!(num_stack > 0, "num_stack must be at least 1");
+		let frame_shape = match env.observation_space() {
+			Space::Box { shape, .. } if shape.len() == 2 => [shape[0], shape[1], 1],
+			Space::Box { shape, .. } if shape.len() == 3 => [shape[0], shape[1], shape[2]],
+			space => return Err(AleError::UnsupportedSpace(space)),
+		};
+		Ok(FrameStack { env, frames: VecDeque::with_capacity(num_stack), num_stack, frame_shape, layout })
+	}
+
+	fn lazy_frames(&self) -> LazyFrames {
+		LazyFrames { frames: self.frames.iter().cloned().collect(), frame_shape: self.frame_shape, layout: self.layout }
+	}
+}
+impl_inner!(FrameStack);
+impl<E: Environment<Observation = Vec<u8>>> Environment for FrameStack<E> {
+	type Observation = LazyFrames;
+
+	fn reset(&mut self, seed: Option<i32>) -> Result<(LazyFrames, ResetInfo)> {
+		let (observation, info) = self.env.reset(seed)?;
+		let frame: Arc<[u8]> = observation.into();
+		self.frames.clear();
+		for _ in 0..self.num_stack {
+			self.frames.push_back(frame.clone());
+		}
+		Ok((self.lazy_frames(), info))
+	}
+
+	/// Steps the environment, and pushes its new observation onto the stack.
+	fn step(&mut self, action: u8) -> Result<StepResult> {
+		let result = self.env.step(action)?;
+		if self.frames.len() == self.num_stack {
+			self.frames.pop_front();
+		}
+		self.frames.push_back(self.env.observation().into());
+		Ok(result)
+	}
+
+	/// Returns the stacked observations. This only clones references to the frames, not the frames themselves.
+	///
+	/// # Panics
+	/// Panics if the environment has not been reset yet.
+	fn observation(&mut self) -> LazyFrames {
+		assert!(!self.frames.is_empty(), "FrameStack must be reset before it is observed");
+		self.lazy_frames()
+	}
+
+	fn observation_space(&self) -> Space { Space::bytes(self.layout.stacked_shape(self.frame_shape, self.num_stack)) }
+
+	fn action_space(&self) -> Space { self.env.action_space() }
+
+	fn action_meanings(&self) -> Option<&[Action]> { self.env.action_meanings() }
+}
+
+/// Observations stacked by [`FrameStack`], oldest first, which share their frames with other stacks.
+///
+/// Consecutive stacks have all but one frame in common, so storing them in e.g. a replay buffer takes about as much
+/// memory as storing single observations. Cloning is cheap, as only the references to the frames are cloned. The
+/// stacked observation is only laid out contiguously by [`LazyFrames::to_vec`] or [`LazyFrames::write_to`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazyFrames {
+	frames: Vec<Arc<[u8]>>,
+	frame_shape: [usize; 3],
+	layout: Layout,
+}
+impl LazyFrames {
+	/// Returns the number of stacked frames.
+	pub fn len(&self) -> usize {
+		self.frames.len()
+	}
+
+	/// Returns whether no frames are stacked, which is never the case for stacks returned by [`FrameStack`].
+	pub fn is_empty(&self) -> bool {
+		self.frames.is_empty()
+	}
+
+	/// Returns a frame, laid out like the observations of the wrapped environment, or `None` if `index` is out of
+	/// bounds.
+	pub fn frame(&self, index: usize) -> Option<&[u8]> {
+		self.frames.get(index).map(|frame| &frame[..])
+	}
+
+	/// Returns an iterator over the frames, oldest first.
+	pub fn frames(&self) -> impl Iterator<Item = &[u8]> + '_ {
+		self.frames.iter().map(|frame| &frame[..])
+	}
+
+	/// Returns the layout of the stacked observation.
+	pub fn layout(&self) -> Layout {
+		self.layout
+	}
+
+	/// Returns the shape of the stacked observation, see [`Layout::stacked_shape`].
+	pub fn shape(&self) -> Vec<usize> {
+		self.layout.stacked_shape(self.frame_shape, self.frames.len())
+	}
+
+	/// Returns the number of bytes in the stacked observation.
+	pub fn size(&self) -> usize {
+		self.frame_shape.iter().product::<usize>() * self.frames.len()
+	}
+
+	/// Copies the stacked observation into the beginning of `buf`, and returns the number of bytes written.
+	///
+	/// Returns [`AleError::BufferTooSmall`] if `buf` is smaller than [`LazyFrames::size`].
+	pub fn write_to(&self, buf: &mut [u8]) -> Result<usize> {
+		let size = self.size();
+		if buf.len() < size {
+			return Err(AleError::BufferTooSmall { expected: size, actual: buf.len() });
+		}
+		let count = self.frames.len();
+		for (index, frame) in self.frames.iter().enumerate() {
+			self.layout.write_stacked(frame, self.frame_shape, index, count, &mut buf[..size]);
+		}
+		Ok(size)
+	}
+
+	/// Returns the stacked observation as a contiguous buffer, laid out row-major according to [`LazyFrames::shape`].
+	pub fn to_vec(&self) -> Vec<u8> {
+		let mut buf = vec![0; self.size()];
+		self.write_to(&mut buf).expect("buffer has the size of the stacked observation");
+		buf
+	}
+}
+impl From<LazyFrames> for Vec<u8> {
+	fn from(frames: LazyFrames) -> Vec<u8> { frames.to_vec() }
+}
+
+/// A wrapped environment with [`Vec<u8>`] observations, as returned by [`AtariPreprocessing::wrap`].
+pub type BoxedEnvironment = Box<dyn Environment<Observation = Vec<u8>> + Send>;
+
+/// The standard preprocessing of Atari games, with the same defaults as Gymnasium's `AtariPreprocessing`.
+///
+/// In order, this takes up to 30 `NOOP` actions on reset, converts observations to grayscale, repeats each action for
+/// 4 frames while max-pooling the last two, and resizes observations to 84x84. Optionally it ends episodes when a life
+/// is lost, like [`EpisodicLife`].
+///
+/// The wrapped environment should not skip frames itself, i.e. its `frame_skip` setting should be `1`, which is the
+/// ALE's default.
+///
+/// # Examples
+/// ```
+/// # use ale::{AleConfig, BundledRom, Environment};
+/// use ale::wrappers::AtariPreprocessing;
+///
+/// let ale = AleConfig::new().build(BundledRom::Pong).unwrap();
+/// let mut env = AtariPreprocessing::new().wrap(ale).unwrap();
+/// let (observation, _) = env.reset(Some(0)).unwrap();
+/// assert_eq!(observation.len(), 84 * 84);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtariPreprocessing {
+	noop_max: u32,
+	frame_skip: u32,
+	screen_size: usize,
+	terminal_on_life_loss: bool,
+	grayscale: bool,
+}
+impl AtariPreprocessing {
+	/// Creates the preprocessing with Gymnasium's defaults.
+	pub fn new() -> AtariPreprocessing {
+		AtariPreprocessing { noop_max: 30, frame_skip: 4, screen_size: 84, terminal_on_life_loss: false, grayscale: true }
+	}
+
+	/// Sets the maximum number of `NOOP` actions taken on reset. Defaults to `30`.
+	pub fn noop_max(mut self, noop_max: u32) -> AtariPreprocessing {
+		self.noop_max = noop_max;
+		self
+	}
+
+	/// Sets the number of frames each action is repeated for. Defaults to `4`.
+	pub fn frame_skip(mut self, frame_skip: u32) -> AtariPreprocessing {
+		self.frame_skip = frame_skip;
+		self
+	}
+
+	/// Sets the height and width observations are resized to. Defaults to `84`.
+	pub fn screen_size(mut self, screen_size: usize) -> AtariPreprocessing {
+		self.screen_size = screen_size;
+		self
+	}
+
+	/// Sets whether episodes end when a life is lost. Defaults to `false`.
+	pub fn terminal_on_life_loss(mut self, terminal_on_life_loss: bool) -> AtariPreprocessing {
+		self.terminal_on_life_loss = terminal_on_life_loss;
+		self
+	}
+
+	/// Sets whether observations are converted to grayscale. Defaults to `true`.
+	pub fn grayscale(mut self, grayscale: bool) -> AtariPreprocessing {
+		self.grayscale = grayscale;
+		self
+	}
+
+	/// Wraps `env` with the preprocessing.
+	///
+	/// Returns [`AleError::UnsupportedSpace`] if the observations of `env` are not RGB images when converting them to
+	/// grayscale, or not images at all.
+	///
+	/// # Panics
+	/// Panics if the frame skip is `0`.
+	pub fn wrap<E>(&self, env: E) -> Result<BoxedEnvironment>
+	where
+		E: Environment<Observation = Vec<u8>> + Send + 'static,
+	{
+		let mut env: BoxedEnvironment = Box::new(NoopReset::new(env, self.noop_max));
+		if self.grayscale {
+			env = Box::new(GrayScale::new(env)?);
+		}
+		env = Box::new(MaxAndSkip::new(env, self.frame_skip));
+		env = Box::new(ResizeObservation::new(env, self.screen_size, self.screen_size)?);
+		if self.terminal_on_life_loss {
+			env = Box::new(EpisodicLife::new(env));
+		}
+		Ok(env)
+	}
+}
+impl Default for AtariPreprocessing {
+	fn default() -> AtariPreprocessing { AtariPreprocessing::new() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// An environment with scripted outcomes, whose observations take the value `fill` after a reset and increase by
+	/// one with every step. With `flicker`, every other byte is `255` instead, alternating with every step.
+	struct FakeEnv {
+		shape: Vec<usize>,
+		action_dim: usize,
+		meanings: Option<Vec<Action>>,
+		/// Outcome of each step since the last reset, which is the default outcome once they run out.
+		outcomes: Vec<StepResult>,
+		fill: u8,
+		flicker: bool,
+		steps: usize,
+		resets: usize,
+		actions: Vec<u8>,
+	}
+	impl FakeEnv {
+		fn new(shape: Vec<usize>) -> FakeEnv {
+			FakeEnv {
+				shape,
+				action_dim: 4,
+				meanings: None,
+				outcomes: vec![],
+				fill: 0,
+				flicker: false,
+				steps: 0,
+				resets: 0,
+				actions: vec![],
+			}
+		}
+	}
+	impl Environment for FakeEnv {
+		type Observation = Vec<u8>;
+
+		fn reset(&mut self, _seed: Option<i32>) -> Result<(Vec<u8>, ResetInfo)> {
+			self.steps = 0;
+			self.resets += 1;
+			Ok((self.observation(), ResetInfo::default()))
+		}
+
+		fn step(&mut self, action: u8) -> Result<StepResult> {
+			assert!((action as usize) < self.action_dim);
+			let result = self.outcomes.get(self.steps).copied().unwrap_or_default();
+			self.steps += 1;
+			self.actions.push(action);
+			Ok(result)
+		}
+
+		fn observation(&mut self) -> Vec<u8> {
+			let value = self.fill.wrapping_add(self.steps as u8);
+			let flickers = |i: usize| self.flicker && i % 2 == self.steps % 2;
+			(0..self.shape.iter().product()).map(|i| if flickers(i) { 255 } else { value }).collect()
+		}
+
+		fn observation_space(&self) -> Space { Space::bytes(self.shape.clone()) }
+
+		fn action_space(&self) -> Space { Space::Discrete { n: self.action_dim } }
+
+		fn action_meanings(&self) -> Option<&[Action]> { self.meanings.as_deref() }
+	}
+
+	fn reward(reward: i32) -> StepResult {
+		StepResult { reward, ..StepResult::default() }
+	}
+
+	#[test]
+	fn area_weights_cover_every_output_pixel() {
+		for &(src, dst) in &[(210, 84), (160, 84), (84, 84), (3, 7), (7, 3)] {
+			let weights = area_weights(src, dst);
+			assert_eq!(weights.len(), dst);
+			for pixel in &weights {
+				assert!(pixel.iter().all(|&(j, weight)| j < src && weight > 0.0));
+				let total: f32 = pixel.iter().map(|&(_, weight)| weight).sum();
+				assert!((total - 1.0).abs() < 1e-4, "weights of {} -> {} sum to {}", src, dst, total);
+			}
+		}
+		let identity = area_weights(84, 84);
+		assert!(identity.iter().enumerate().all(|(i, pixel)| pixel[..] == [(i, 1.0)]));
+	}
+
+	#[test]
+	fn resize_keeps_constant_images_constant() {
+		for shape in [vec![210, 160], vec![210, 160, 3]].iter() {
+			let channels = shape.get(2).copied().unwrap_or(1);
+			let mut env = ResizeObservation::new(FakeEnv { fill: 123, ..FakeEnv::new(shape.clone()) }, 84, 84).unwrap();
+			let mut expected_shape = shape.clone();
+			expected_shape[..2].copy_from_slice(&[84, 84]);
+			assert_eq!(env.observation_space().shape(), &expected_shape[..]);
+			let (observation, _) = env.reset(None).unwrap();
+			assert_eq!(observation.len(), 84 * 84 * channels);
+			assert!(observation.iter().all(|&value| value == 123));
+			env.step(0).unwrap();
+			assert!(env.observation().iter().all(|&value| value == 124));
+		}
+	}
+
+	#[test]
+	fn resize_rejects_non_images() {
+		let env = FakeEnv::new(vec![128]);
+		assert!(matches!(ResizeObservation::new(env, 84, 84), Err(AleError::UnsupportedSpace(_))));
+	}
+
+	#[test]
+	fn clip_reward_keeps_the_sign() {
+		let rewards = [-5, 0, 3, 1, -1, 1000];
+		let outcomes = rewards.iter().map(|&r| reward(r)).collect();
+		let mut env = ClipReward::new(FakeEnv { outcomes, ..FakeEnv::new(vec![2, 2]) });
+		env.reset(None).unwrap();
+		let clipped: Vec<i32> = rewards.iter().map(|_| env.step(0).unwrap().reward).collect();
+		assert_eq!(clipped, [-1, 0, 1, 1, -1, 1]);
+	}
+
+	#[test]
+	fn fire_reset_needs_the_fire_action() {
+		let env = FakeEnv { action_dim: 2, ..FakeEnv::new(vec![2, 2]) };
+		assert!(matches!(FireReset::new(env), Err(AleError::NoFireAction { action_dim: 2, action: None })));
+		let meanings = Some(vec![Action::Noop, Action::Up, Action::Down]);
+		let env = FakeEnv { action_dim: 3, meanings, ..FakeEnv::new(vec![2, 2]) };
+		assert!(matches!(FireReset::new(env), Err(AleError::NoFireAction { action: Some(Action::Up), .. })));
+		for meanings in [None, Some(vec![Action::Noop, Action::Fire, Action::Up])].iter().cloned() {
+			let mut env = FireReset::new(FakeEnv { action_dim: 3, meanings, ..FakeEnv::new(vec![2, 2]) }).unwrap();
+			env.reset(None).unwrap();
+			assert_eq!(env.inner().actions, [1, 2]);
+		}
+	}
+
+	#[test]
+	fn max_and_skip_sums_rewards_and_maxes_the_last_two_frames() {
+		let outcomes = vec![reward(1), StepResult { life_lost: true, ..reward(2) }, reward(-4), reward(8), reward(16)];
+		let mut env = MaxAndSkip::new(FakeEnv { outcomes, flicker: true, ..FakeEnv::new(vec![2, 3]) }, 4);
+		env.reset(None).unwrap();
+		let result = env.step(3).unwrap();
+		assert_eq!((result.reward, result.life_lost), (7, true));
+		assert_eq!(env.inner().actions, [3; 4]);
+		// Every pixel is 255 in exactly one of the last two frames
+		assert_eq!(env.observation(), [255; 6]);
+		let result = env.step(0).unwrap();
+		assert_eq!((result.reward, result.life_lost), (16, false));
+	}
+
+	#[test]
+	fn max_and_skip_stops_when_the_episode_is_over() {
+		let outcomes = vec![reward(1), StepResult { terminated: true, ..reward(2) }, reward(4)];
+		let mut env = MaxAndSkip::new(FakeEnv { outcomes, flicker: true, ..FakeEnv::new(vec![2, 3]) }, 4);
+		env.reset(None).unwrap();
+		let result = env.step(1).unwrap();
+		assert_eq!((result.reward, result.terminated), (3, true));
+		assert_eq!(env.inner().steps, 2);
+		// The episode ended before the second to last step, so only the last frame is observed
+		assert_eq!(env.observation(), [255, 2, 255, 2, 255, 2]);
+	}
+
+	#[test]
+	fn episodic_life_ends_episodes_on_life_loss() {
+		let life_lost = StepResult { life_lost: true, lives: 2, ..StepResult::default() };
+		let game_over = StepResult { life_lost: true, terminated: true, ..StepResult::default() };
+		let outcomes = vec![life_lost, StepResult { lives: 2, ..StepResult::default() }, game_over];
+		let mut env = EpisodicLife::new(FakeEnv { outcomes, ..FakeEnv::new(vec![2, 2]) });
+		env.reset(None).unwrap();
+		assert_eq!(env.inner().resets, 1);
+
+		let result = env.step(3).unwrap();
+		assert!(result.terminated && result.life_lost);
+		assert!(!env.is_game_over());
+		// Continues with a NOOP rather than resetting the game
+		let (observation, _) = env.reset(None).unwrap();
+		assert_eq!(observation, [2; 4]);
+		assert_eq!(env.inner().resets, 1);
+		assert_eq!(env.inner().actions, [3, 0]);
+
+		assert!(env.step(3).unwrap().terminated);
+		assert!(env.is_game_over());
+		env.reset(None).unwrap();
+		assert_eq!(env.inner().resets, 2);
+
+		// A seed always resets the game
+		env.step(3).unwrap();
+		assert!(!env.is_game_over());
+		env.reset(Some(0)).unwrap();
+		assert_eq!(env.inner().resets, 3);
+		assert_eq!(env.inner().actions, [3, 0, 3, 3]);
+	}
+
+	#[test]
+	fn gray_scale_converts_rgb_pixels() {
+		let mut env = GrayScale::new(FakeEnv { fill: 40, flicker: true, ..FakeEnv::new(vec![2, 3, 3]) }).unwrap();
+		assert_eq!(env.observation_space().shape(), [2, 3]);
+		let (observation, _) = env.reset(None).unwrap();
+		let rgb = env.inner_mut().observation();
+		let expected: Vec<u8> = rgb.chunks(3).map(|pixel| luminance([pixel[0], pixel[1], pixel[2]])).collect();
+		assert_eq!(observation, expected);
+		assert_eq!(observation.len(), 6);
+		assert!(observation.iter().any(|&pixel| pixel != observation[0]));
+
+		for shape in [vec![2, 3], vec![2, 3, 4]].iter() {
+			let env = FakeEnv::new(shape.clone());
+			assert!(matches!(GrayScale::new(env), Err(AleError::UnsupportedSpace(_))));
+		}
+	}
+
+	#[test]
+	fn noop_reset_is_reproducible() {
+		let noops = |seed| {
+			let mut env = NoopReset::new(FakeEnv::new(vec![2, 2]), 30);
+			(0..5)
+				.map(|i| {
+					env.reset(if i == 0 { seed } else { None }).unwrap();
+					env.inner().steps
+				})
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(noops(None), noops(None));
+		assert_eq!(noops(Some(7)), noops(Some(7)));
+		assert_ne!(noops(Some(7)), noops(Some(8)));
+		assert!(noops(Some(7)).iter().all(|&steps| (1..=30).contains(&steps)));
+	}
+
+	#[test]
+	fn frame_stack_shares_frames() {
+		let mut env = FrameStack::new(FakeEnv::new(vec![2, 3]), 4, Layout::ChannelsFirst).unwrap();
+		let (first, _) = env.reset(None).unwrap();
+		assert!(first.frames.iter().all(|frame| Arc::ptr_eq(frame, &first.frames[0])));
+		let mut previous = first;
+		for step in 1..=5u8 {
+			env.step(0).unwrap();
+			let frames = env.observation();
+			assert_eq!(frames.len(), 4);
+			for (old, new) in previous.frames[1..].iter().zip(&frames.frames) {
+				assert!(Arc::ptr_eq(old, new), "frame was copied instead of shared");
+			}
+			assert_eq!(frames.frame(3), Some(&[step; 6][..]));
+			previous = frames;
+		}
+		assert_eq!(previous.frames().map(|frame| frame[0]).collect::<Vec<_>>(), [2, 3, 4, 5]);
+	}
+
+	#[test]
+	fn lazy_frames_match_stacked_shape() {
+		for &layout in &[Layout::ChannelsLast, Layout::ChannelsFirst] {
+			for shape in [vec![2, 3], vec![2, 3, 3]].iter() {
+				let frame_shape = [shape[0], shape[1], shape.get(2).copied().unwrap_or(1)];
+				let mut env = FrameStack::new(FakeEnv::new(shape.clone()), 4, layout).unwrap();
+				env.reset(None).unwrap();
+				for _ in 0..3 {
+					env.step(0).unwrap();
+				}
+				let frames = env.observation();
+				assert_eq!(frames.shape(), layout.stacked_shape(frame_shape, 4));
+				assert_eq!(env.observation_space().shape(), &frames.shape()[..]);
+				let stacked = frames.to_vec();
+				assert_eq!(stacked.len(), frames.size());
+				assert_eq!(stacked.len(), frames.shape().iter().product::<usize>());
+				let expected: Vec<u8> = match layout {
+					Layout::ChannelsFirst => frames.frames().flat_map(|frame| frame.iter().copied()).collect(),
+					Layout::ChannelsLast => {
+						let channels = frame_shape[2];
+						(0..frame_shape[0] * frame_shape[1])
+							.flat_map(|pixel| {
+								frames.frames().flat_map(move |frame| &frame[pixel * channels..][..channels])
+							})
+							.copied()
+							.collect()
+					}
+				};
+				assert_eq!(stacked, expected);
+				let mut buf = vec![0; stacked.len() - 1];
+				assert!(matches!(frames.write_to(&mut buf), Err(AleError::BufferTooSmall { .. })));
+			}
+		}
+	}
+}
@@ -0,0 +1,85 @@
+//! Checks that stepping a `VecEnv` from within the Rayon thread pool makes no Rust heap allocations, including when
+//! environments are reset.
+//!
+//! Only allocations through Rust's global allocator are counted, so memory that the ALE allocates in C++ is not.
+
+use std::alloc::{GlobalAlloc, Layout as AllocLayout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ale::{Ale, AleConfig, AutoReset, BundledRom, Layout, LoggerMode, VecEnv};
+
+const NUM_ENVS: usize = 128;
+
+/// Counts every allocation made through Rust's global allocator by any thread.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: AllocLayout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+		System.alloc(layout)
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: AllocLayout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+		System.alloc_zeroed(layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: AllocLayout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+		System.realloc(ptr, layout, new_size)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: AllocLayout) { System.dealloc(ptr, layout) }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Steps every environment, and resets those that are done by hand if they are not reset automatically.
+fn step(envs: &mut VecEnv, actions: &[u8]) {
+	let step = envs.step(actions).unwrap();
+	let mut done = [false; NUM_ENVS];
+	for (i, done) in done.iter_mut().enumerate() {
+		*done = step.done(i);
+	}
+	if envs.auto_reset() == AutoReset::Disabled {
+		for i in (0..NUM_ENVS).filter(|&i| done[i]) {
+			envs.reset_env(i, None).unwrap();
+		}
+	}
+}
+
+#[test]
+fn step_in_pool_makes_no_rust_allocations() {
+	Ale::set_logger_mode(LoggerMode::Error);
+	// Short episodes, so that every environment is reset several times while allocations are counted
+	let config = AleConfig::new().seed(0).truncate_episodes_after(60);
+	let mut envs = VecEnv::new(&config, BundledRom::Breakout, NUM_ENVS).unwrap();
+	let action_dim = envs.envs()[0].action_dim();
+	let actions: Vec<u8> = (0..NUM_ENVS).map(|i| i as u8 % action_dim).collect();
+	// Jobs injected into a pool from outside of it are queued in blocks that Rayon allocates as needed, so the steps
+	// are taken from one of the pool's own threads, like they would be in a training loop running on the pool
+	let pool = rayon::ThreadPoolBuilder::new().build().unwrap();
+
+	for &auto_reset in &[AutoReset::NextStep, AutoReset::SameStep, AutoReset::Disabled] {
+		for &layout in &[Layout::ChannelsLast, Layout::ChannelsFirst] {
+			envs.set_auto_reset(auto_reset);
+			envs.set_layout(layout);
+			envs.reset(None).unwrap();
+			let allocations = pool.install(|| {
+				// The work queues of Rayon's threads grow to their working size during the first steps
+				for _ in 0..100 {
+					step(&mut envs, &actions);
+				}
+				let before = ALLOCATIONS.load(Ordering::SeqCst);
+				for _ in 0..200 {
+					step(&mut envs, &actions);
+				}
+				ALLOCATIONS.load(Ordering::SeqCst) - before
+			});
+			assert_eq!(allocations, 0, "stepping made Rust allocations with {:?} and {:?}", auto_reset, layout);
+		}
+	}
+}
@@ -0,0 +1,242 @@
+//! Generates `BundledRom` and the ROM cargo features from `roms/manifest.toml`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Deserialize;
+
+const MANIFEST_PATH: &'static str = "roms/manifest.toml";
+const GENERATED_PATH: &'static str = "src/rom/generated.rs";
+const FEATURES_BEGIN: &'static str = "# BEGIN ROM FEATURES: generated by `cargo xtask gen-roms`, do not edit by hand.";
+const FEATURES_END: &'static str = "# END ROM FEATURES";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+	#[serde(default)]
+	group: BTreeMap<String, Group>,
+	rom: Vec<Rom>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Group {
+	description: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rom {
+	variant: String,
+	file: String,
+	title: String,
+	#[serde(default)]
+	groups: Vec<String>,
+}
+impl Rom {
+	fn id(&self) -> &str {
+		self.file.trim_end_matches(".bin")
+	}
+
+	fn feature(&self) -> String {
+		format!("rom-{}", self.id().replace('_', "-"))
+	}
+}
+
+/// Reads the manifest and the ROMs it lists from `root`, and rewrites the generated sources.
+pub fn run(root: &Path) -> Result<(), String> {
+	let manifest_path = root.join(MANIFEST_PATH);
+	let manifest = std::fs::read_to_string(&manifest_path)
+		.map_err(|e| format!("failed to read {}: {}", manifest_path.display(), e))?;
+	let mut manifest: Manifest = toml::from_str(&manifest)
+		.map_err(|e| format!("failed to parse {}: {}", manifest_path.display(), e))?;
+	manifest.rom.sort_by(|a, b| a.id().cmp(b.id()));
+	validate(&manifest)?;
+
+	let mut md5s = vec![];
+	for rom in &manifest.rom {
+		let path = root.join("roms").join(&rom.file);
+		let data = std::fs::read(&path).map_err(|e| {
+			format!("failed to read {}: {} (run `cargo xtask download-roms` first?)", path.display(), e)
+		})?;
+		md5s.push(format!("{:x}", md5::compute(&data)));
+	}
+
+	let generated_path = root.join(GENERATED_PATH);
+	std::fs::write(&generated_path, generate_source(&manifest, &md5s))
+		.map_err(|e| format!("failed to write {}: {}", generated_path.display(), e))?;
+
+	let cargo_path = root.join("Cargo.toml");
+	let cargo = std::fs::read_to_string(&cargo_path)
+		.map_err(|e| format!("failed to read {}: {}", cargo_path.display(), e))?;
+	let begin = cargo.find(FEATURES_BEGIN);
+	let end = cargo.find(FEATURES_END);
+	let (begin, end) = match (begin, end) {
+		(Some(begin), Some(end)) if begin < end => (begin, end + FEATURES_END.len()),
+		_ => return Err(format!("{} does not contain the ROM feature markers", cargo_path.display())),
+	};
+	let cargo = format!("{}{}{}", &cargo[..begin], generate_features(&manifest), &cargo[end..]);
+	std::fs::write(&cargo_path, cargo).map_err(|e| format!("failed to write {}: {}", cargo_path.display(), e))?;
+
+	Ok(())
+}
+
+fn validate(manifest: &Manifest) -> Result<(), String> {
+	let mut variants = BTreeMap::new();
+	let mut ids = BTreeMap::new();
+	for rom in &manifest.rom {
+		let valid_variant = rom.variant.chars().next().map_or(false, |c| c.is_ascii_uppercase())
+			&& rom.variant.chars().all(|c| c.is_ascii_alphanumeric());
+		if !valid_variant {
+			return Err(format!("invalid variant name {:?}", rom.variant));
+		}
+		let valid_file = rom.file.ends_with(".bin")
+			&& !rom.id().is_empty()
+			&& rom.id().chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+		if !valid_file {
+			return Err(format!("invalid file name {:?} for {}, expected e.g. \"ms_pacman.bin\"", rom.file, rom.variant));
+		}
+		if let Some(other) = variants.insert(&rom.variant, &rom.file) {
+			return Err(format!("variant {} is used by both {} and {}", rom.variant, other, rom.file));
+		}
+		if let Some(other) = ids.insert(rom.id(), &rom.variant) {
+			return Err(format!("file {} is used by both {} and {}", rom.file, other, rom.variant));
+		}
+		for group in &rom.groups {
+			if !manifest.group.contains_key(group) {
+				return Err(format!("{} is part of undeclared group {:?}", rom.variant, group));
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Returns the name of the `BundledRom` constant listing the games of a group, e.g. `ATARI_57` for `atari57`.
+fn group_const(group: &str) -> String {
+	let mut name = String::new();
+	let mut prev = None;
+	for c in group.chars() {
+		if c == '-' || c == '_' {
+			name.push('_');
+		} else {
+			if c.is_ascii_digit() && prev.map_or(false, |p: char| p.is_ascii_alphabetic()) {
+				name.push('_');
+			}
+			name.push(c.to_ascii_uppercase());
+		}
+		prev = Some(c);
+	}
+	name
+}
+
+fn generate_source(manifest: &Manifest, md5s: &[String]) -> String {
+	let roms = &manifest.rom;
+	let mut s = String::new();
+	s.push_str("// Generated by `cargo xtask gen-roms` from `roms/manifest.toml`, do not edit by hand.\n\n");
+	s.push_str("/// Enum of ROMs that come bundled with the libarary.\n");
+	s.push_str("///\n");
+	s.push_str(
+		"/// Every game is listed regardless of which ROMs were compiled in. See [`BundledRom::data`] for how to select them.\n",
+	);
+	s.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n");
+	s.push_str("pub enum BundledRom {\n");
+	for rom in roms {
+		writeln!(s, "\t{},", rom.variant).unwrap();
+	}
+	s.push_str("}\n");
+	s.push_str("impl BundledRom {\n");
+
+	s.push_str("\t/// Every bundled ROM, in alphabetical order.\n");
+	writeln!(s, "\tpub const ALL: [BundledRom; {}] = [", roms.len()).unwrap();
+	for rom in roms {
+		writeln!(s, "\t\tBundledRom::{},", rom.variant).unwrap();
+	}
+	s.push_str("\t];\n");
+
+	for (group, info) in &manifest.group {
+		let members: Vec<_> = roms.iter().filter(|rom| rom.groups.contains(group)).collect();
+		writeln!(s).unwrap();
+		writeln!(s, "\t/// {}", info.description).unwrap();
+		writeln!(s, "\t///").unwrap();
+		writeln!(s, "\t/// These are the ROMs enabled by the `{}` feature.", group).unwrap();
+		writeln!(s, "\tpub const {}: [BundledRom; {}] = [", group_const(group), members.len()).unwrap();
+		for rom in members {
+			writeln!(s, "\t\tBundledRom::{},", rom.variant).unwrap();
+		}
+		s.push_str("\t];\n");
+	}
+
+	let mut match_fn = |doc: &[&str], name: &str, value: &dyn Fn(usize, &Rom) -> String| {
+		writeln!(s).unwrap();
+		for line in doc {
+			writeln!(s, "\t///{}{}", if line.is_empty() { "" } else { " " }, line).unwrap();
+		}
+		writeln!(s, "\tpub fn {}(&self) -> &'static str {{", name).unwrap();
+		s.push_str("\t\tuse BundledRom::*;\n");
+		s.push_str("\t\tmatch self {\n");
+		for (i, rom) in roms.iter().enumerate() {
+			writeln!(s, "\t\t\t{} => {:?},", rom.variant, value(i, rom)).unwrap();
+		}
+		s.push_str("\t\t}\n");
+		s.push_str("\t}\n");
+	};
+	match_fn(
+		&[
+			"Returns the filename that the ROM should be named, in order for the ALE to pick up on it and",
+			"use the correct settings.",
+		],
+		"filename",
+		&|_, rom| rom.file.clone(),
+	);
+	match_fn(&["Returns the human readable title of the game, e.g. `\"Ms. Pac-Man\"`."], "title", &|_, rom| {
+		rom.title.clone()
+	});
+	match_fn(
+		&["Returns the MD5 checksum of the ROM, as a lowercase hex string. This is what the ALE uses to identify games."],
+		"md5",
+		&|i, _| md5s[i].clone(),
+	);
+	match_fn(&["Returns the cargo feature that bundles this ROM, e.g. `\"rom-ms-pacman\"`."], "feature", &|_, rom| {
+		rom.feature()
+	});
+
+	s.push_str("\n");
+	s.push_str("\t/// Returns the ROM's data if it was included uncompressed in the binary.\n");
+	s.push_str("\t#[cfg(not(feature = \"compressed-roms\"))]\n");
+	s.push_str("\tpub(super) fn included_data(&self) -> Option<&'static [u8]> {\n");
+	s.push_str("\t\t#[allow(unused_imports)]\n");
+	s.push_str("\t\tuse BundledRom::*;\n");
+	s.push_str("\t\tmatch self {\n");
+	for rom in roms {
+		writeln!(s, "\t\t\t#[cfg(all(feature = {:?}, not(feature = \"no-bundled-roms\")))]", rom.feature()).unwrap();
+		writeln!(s, "\t\t\t{} => Some(include_bytes!(\"../../roms/{}\")),", rom.variant, rom.file).unwrap();
+	}
+	s.push_str("\t\t\t#[allow(unreachable_patterns)]\n");
+	s.push_str("\t\t\t_ => None,\n");
+	s.push_str("\t\t}\n");
+	s.push_str("\t}\n");
+	s.push_str("}\n");
+	s
+}
+
+fn generate_features(manifest: &Manifest) -> String {
+	let mut s = String::new();
+	writeln!(s, "{}", FEATURES_BEGIN).unwrap();
+	let mut group = |name: &str, features: Vec<String>| {
+		writeln!(s, "{} = [", name).unwrap();
+		for feature in features {
+			writeln!(s, "\t\"{}\",", feature).unwrap();
+		}
+		s.push_str("]\n");
+	};
+	group("all-roms", manifest.rom.iter().map(Rom::feature).collect());
+	for name in manifest.group.keys() {
+		group(name, manifest.rom.iter().filter(|rom| rom.groups.contains(name)).map(Rom::feature).collect());
+	}
+	for rom in &manifest.rom {
+		writeln!(s, "{} = []", rom.feature()).unwrap();
+	}
+	s.push_str(FEATURES_END);
+	s
+}
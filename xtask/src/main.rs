@@ -11,6 +11,8 @@ use base64::{decode, DecodeError};
 use std::io::{self, Read, Write};
 use walkdir::WalkDir;
 
+mod gen_roms;
+
 const ATARI_ROMS_URL: &'static str = "https://gist.githubusercontent.com/jjshoots/61b22aefce4456920ba99f2c36906eda/raw/00046ac3403768bfe45857610a3d333b8e35e026/Roms.tar.gz.b64";
 const ATARI_B64_TAR_FILENAME: &'static str = "Roms.tar.gz.b64";
 const ATARI_TAR_FILENAME: &'static str = "Roms.tar.gz";
@@ -27,6 +29,8 @@ fn main() {
 			.about("Generate Arcade Learning Environment bindings"))
 		.subcommand(SubCommand::with_name("download-roms")
 			.about("Download builtin Atari ROMs, and place in the roms/ folder"))
+		.subcommand(SubCommand::with_name("gen-roms")
+			.about("Generate the BundledRom enum and the ROM features from roms/manifest.toml"))
 		.subcommand(SubCommand::with_name("clean")
 			.about("Remove the target directories")
 			.arg(Arg::with_name("all")
@@ -39,6 +43,13 @@ fn main() {
 		eprintln!("{}download-roms", XTASK_PREFIX);
 		run_download_roms();
 
+	} else if let Some(_) = matches.subcommand_matches("gen-roms") {
+		eprintln!("{}gen-roms", XTASK_PREFIX);
+		if let Err(e) = gen_roms::run(&project_root()) {
+			eprintln!("{}{}", ERROR_PREFIX, e);
+			std::process::exit(1);
+		}
+
 	} else if let Some(matches) = matches.subcommand_matches("clean") {
 		eprintln!("{}clean", XTASK_PREFIX);
 		let mut rets = vec![